@@ -0,0 +1,406 @@
+//! Byte-distribution statistics in the manner of the `ent` program by John Walker.
+
+use std::collections::VecDeque;
+use std::io::{self, Read};
+
+use serde::Serialize;
+
+use crate::special;
+
+/// The number of bytes of each Monte Carlo sample: three for each coordinate.
+const MONTE_CARLO_BYTES: usize = 6;
+
+/// The squared radius of the circle inscribed in the square of 24-bit coordinates.
+const MONTE_CARLO_RADIUS: f64 = 16_777_215.0 * 16_777_215.0;
+
+/// Accumulates the statistics of a stream of bytes.
+///
+/// Bytes may be added with [`Analyzer::update`] or by writing to the analyzer, so that a reader can be
+/// analyzed with [`std::io::copy`].
+#[derive(Debug, Clone)]
+pub struct Analyzer {
+    histogram: [u64; 256],
+    length: u64,
+    sum: u64,
+
+    /// The sum of the products of successive bytes, for the serial correlation.
+    products: f64,
+    first: Option<u8>,
+    last: Option<u8>,
+
+    sample: [u8; MONTE_CARLO_BYTES],
+    sampled: usize,
+    samples: u64,
+    inside: u64
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Analyzer {
+            histogram: [0; 256],
+            length: 0,
+            sum: 0,
+            products: 0.0,
+            first: None,
+            last: None,
+            sample: [0; MONTE_CARLO_BYTES],
+            sampled: 0,
+            samples: 0,
+            inside: 0
+        }
+    }
+}
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Analyzer::default()
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.histogram[byte as usize] += 1;
+            self.length += 1;
+            self.sum += byte as u64;
+
+            match self.last {
+                Some(last) => self.products += last as f64 * byte as f64,
+                None => self.first = Some(byte)
+            }
+
+            self.last = Some(byte);
+            self.sample[self.sampled] = byte;
+            self.sampled += 1;
+
+            if self.sampled == MONTE_CARLO_BYTES {
+                let x = u32::from_be_bytes([0, self.sample[0], self.sample[1], self.sample[2]]) as f64;
+                let y = u32::from_be_bytes([0, self.sample[3], self.sample[4], self.sample[5]]) as f64;
+
+                self.samples += 1;
+                if x * x + y * y <= MONTE_CARLO_RADIUS { self.inside += 1 }
+                self.sampled = 0;
+            }
+        }
+    }
+
+    pub fn finish(&self) -> Report {
+        let length = self.length as f64;
+        let expected = length / 256.0;
+
+        // An empty sum is negative zero, so empty input is given an entropy of zero explicitly.
+        let entropy = match self.length {
+            0 => 0.0,
+            _ => self.histogram.iter()
+                .filter(|&&count| count > 0)
+                .map(|&count| count as f64 / length)
+                .map(|probability| probability * (1.0 / probability).log2())
+                .sum::<f64>()
+        };
+
+        let chi_square = match self.length {
+            0 => 0.0,
+            _ => self.histogram.iter().map(|&count| (count as f64 - expected).powi(2) / expected).sum()
+        };
+
+        let squares: f64 = self.histogram.iter().enumerate().map(|(byte, &count)| count as f64 * (byte as f64).powi(2)).sum();
+
+        // The sequence is treated as circular, so the last byte is paired with the first.
+        let products = match (self.first, self.last) {
+            (Some(first), Some(last)) => self.products + first as f64 * last as f64,
+            _ => 0.0
+        };
+
+        let sum = self.sum as f64;
+        let denominator = length * squares - sum * sum;
+
+        let serial_correlation = match denominator {
+            0.0 => None,
+            _ => Some((length * products - sum * sum) / denominator)
+        };
+
+        let pi = match self.samples {
+            0 => None,
+            samples => Some(4.0 * self.inside as f64 / samples as f64)
+        };
+
+        Report {
+            length: self.length,
+            entropy,
+            compression: (self.length > 0).then_some((8.0 - entropy) / 8.0),
+            chi_square,
+            chi_square_p_value: if self.length == 0 { 1.0 } else { special::chi_square_upper(chi_square, 255.0) },
+            mean: if self.length == 0 { 0.0 } else { sum / length },
+            monte_carlo_pi: pi,
+            monte_carlo_pi_error: pi.map(|pi| (pi - std::f64::consts::PI).abs() / std::f64::consts::PI),
+            serial_correlation,
+            histogram: self.histogram.to_vec()
+        }
+    }
+}
+
+impl std::io::Write for Analyzer {
+    fn write(&mut self, buffer: &[u8]) -> std::io::Result<usize> {
+        self.update(buffer);
+
+        Ok(buffer.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The statistics of a sequence of bytes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// The number of bytes.
+    pub length: u64,
+
+    /// The Shannon entropy in bits per byte, between 0 and 8.
+    pub entropy: f64,
+
+    /// The fraction by which optimal compression of independent bytes would reduce the size, or
+    /// [`None`] if there are no bytes.
+    pub compression: Option<f64>,
+
+    /// The chi-square statistic of the byte counts against a uniform distribution.
+    pub chi_square: f64,
+
+    /// The probability that uniformly random bytes would produce a larger chi-square statistic.
+    pub chi_square_p_value: f64,
+
+    /// The arithmetic mean of the bytes, which is 127.5 for uniformly random bytes.
+    pub mean: f64,
+
+    /// An estimate of pi from points whose coordinates are successive 24-bit integers.
+    pub monte_carlo_pi: Option<f64>,
+
+    /// The relative error of the estimate of pi.
+    pub monte_carlo_pi_error: Option<f64>,
+
+    /// The serial correlation coefficient of successive bytes, or [`None`] if every byte is the same.
+    pub serial_correlation: Option<f64>,
+
+    /// The number of occurrences of each byte value.
+    pub histogram: Vec<u64>
+}
+
+/// Analyze `data` in one call.
+pub fn analyze(data: &[u8]) -> Report {
+    let mut analyzer = Analyzer::new();
+
+    analyzer.update(data);
+    analyzer.finish()
+}
+
+/// The statistics of one window of a larger sequence.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Window {
+    /// The offset of the window in bytes.
+    pub offset: usize,
+
+    pub length: usize,
+    pub entropy: f64,
+    pub chi_square: f64,
+    pub chi_square_p_value: f64,
+    pub mean: f64
+}
+
+/// Analyze windows of `size` bytes of `reader` starting every `step` bytes. The final window may be
+/// shorter.
+///
+/// Only the current window is held in memory, so streams of any length may be analyzed.
+pub fn windows<R: Read>(reader: R, size: usize, step: usize) -> Windows<R> {
+    let size = size.max(1);
+
+    Windows { reader, size, step: step.max(1), buffer: VecDeque::with_capacity(size), offset: 0, started: false, finished: false }
+}
+
+/// An iterator over the statistics of the windows of a stream, returned by [`windows`].
+#[derive(Debug)]
+pub struct Windows<R> {
+    reader: R,
+    size: usize,
+    step: usize,
+
+    /// The bytes of the current window, which begins at `offset`.
+    buffer: VecDeque<u8>,
+    offset: usize,
+    started: bool,
+    finished: bool
+}
+
+impl<R: Read> Windows<R> {
+    /// Move to the next window, returning the number of bytes read which were not in the previous window.
+    fn advance(&mut self) -> io::Result<usize> {
+        if self.started {
+            let skip = self.step.saturating_sub(self.buffer.len());
+
+            self.buffer.drain(..self.step.min(self.buffer.len()));
+            self.offset += self.step;
+
+            if skip > 0 && io::copy(&mut (&mut self.reader).take(skip as u64), &mut io::sink())? < skip as u64 {
+                return Ok(0)
+            }
+        }
+
+        self.started = true;
+
+        let mut fresh = 0;
+        let mut chunk = [0u8; 64 * 1024];
+
+        while self.buffer.len() < self.size {
+            let wanted = (self.size - self.buffer.len()).min(chunk.len());
+
+            match self.reader.read(&mut chunk[..wanted]) {
+                Ok(0) => { self.finished = true; break }
+                Ok(read) => { self.buffer.extend(&chunk[..read]); fresh += read }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error)
+            }
+        }
+
+        Ok(fresh)
+    }
+}
+
+impl<R: Read> Iterator for Windows<R> {
+    type Item = io::Result<Window>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // A window is only reported if it contains bytes beyond the end of the previous window.
+        if self.finished && self.started { return None }
+
+        match self.advance() {
+            Ok(0) => { self.finished = true; return None }
+            Ok(_) => {}
+            Err(error) => { self.finished = true; return Some(Err(error)) }
+        }
+
+        let mut analyzer = Analyzer::new();
+        let (front, back) = self.buffer.as_slices();
+
+        analyzer.update(front);
+        analyzer.update(back);
+
+        let report = analyzer.finish();
+
+        Some(Ok(Window {
+            offset: self.offset,
+            length: self.buffer.len(),
+            entropy: report.entropy,
+            chi_square: report.chi_square,
+            chi_square_p_value: report.chi_square_p_value,
+            mean: report.mean
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Analyzer, Window};
+
+    #[test]
+    fn every_byte_once_has_maximal_entropy() {
+        let data: Vec<u8> = (0..=255).collect();
+        let report = super::analyze(&data);
+
+        assert!((report.entropy - 8.0).abs() < 1e-12);
+        assert_eq!(report.chi_square, 0.0);
+        assert_eq!(report.mean, 127.5);
+        assert!(report.histogram.iter().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn constant_data_has_no_entropy() {
+        let report = super::analyze(&[0x41; 1000]);
+
+        assert_eq!(report.entropy, 0.0);
+        assert_eq!(report.compression, Some(1.0));
+        assert_eq!(report.serial_correlation, None);
+        assert!(report.chi_square_p_value < 1e-12);
+    }
+
+    #[test]
+    fn empty_data_has_no_entropy_or_compression() {
+        let report = super::analyze(&[]);
+
+        assert_eq!(report.length, 0);
+        assert!(report.entropy == 0.0 && report.entropy.is_sign_positive());
+        assert_eq!(report.compression, None);
+        assert_eq!(report.mean, 0.0);
+    }
+
+    #[test]
+    fn detects_serial_correlation() {
+        let rising: Vec<u8> = (0..4096).map(|index| (index / 16) as u8).collect();
+        let alternating: Vec<u8> = (0..4096).map(|index| if index % 2 == 0 { 0 } else { 255 }).collect();
+
+        assert!(super::analyze(&rising).serial_correlation.unwrap() > 0.9);
+        assert!((super::analyze(&alternating).serial_correlation.unwrap() + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn estimates_pi() {
+        // Coordinates which are both zero lie inside the circle, and both maximal lie outside.
+        let mut data = vec![0u8; 6];
+        data.extend([0xff; 6]);
+
+        assert_eq!(super::analyze(&data).monte_carlo_pi, Some(2.0));
+        assert_eq!(super::analyze(&[0; 5]).monte_carlo_pi, None);
+    }
+
+    #[test]
+    fn analyzes_streams_incrementally() {
+        let data: Vec<u8> = (0..10_000u32).map(|index| (index.wrapping_mul(2_654_435_761) >> 24) as u8).collect();
+        let mut analyzer = Analyzer::new();
+
+        for chunk in data.chunks(7) { analyzer.update(chunk) }
+
+        assert_eq!(analyzer.finish(), super::analyze(&data));
+    }
+
+    #[test]
+    fn can_analyze_windows() {
+        let mut data = vec![0u8; 512];
+        data.extend((0..=255).chain(0..=255));
+
+        let windows: Vec<Window> = super::windows(&data[..], 256, 256).map(Result::unwrap).collect();
+
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[1].entropy, 0.0);
+        assert!((windows[2].entropy - 8.0).abs() < 1e-12);
+        assert_eq!(windows[3], window(&data, 768, 256));
+
+        let lengths = |size, step| super::windows(&data[..], size, step).map(|window| window.unwrap().length).collect::<Vec<_>>();
+
+        assert_eq!(lengths(1000, 100), [1000, 924]);
+        assert_eq!(lengths(256, 1024), [256]);
+        assert_eq!(lengths(256, 600), [256, 256]);
+        assert_eq!(super::windows(&[][..], 256, 256).count(), 0);
+    }
+
+    #[test]
+    fn overlapping_windows_match_slices() {
+        let data: Vec<u8> = (0..10_000u32).map(|index| (index.wrapping_mul(2_654_435_761) >> 24) as u8).collect();
+        let windows: Vec<Window> = super::windows(&data[..], 1000, 300).map(Result::unwrap).collect();
+
+        assert_eq!(windows.len(), 31);
+
+        for expected in &windows {
+            assert_eq!(*expected, window(&data, expected.offset, expected.length));
+        }
+    }
+
+    fn window(data: &[u8], offset: usize, length: usize) -> Window {
+        let report = super::analyze(&data[offset..offset + length]);
+
+        Window {
+            offset,
+            length,
+            entropy: report.entropy,
+            chi_square: report.chi_square,
+            chi_square_p_value: report.chi_square_p_value,
+            mean: report.mean
+        }
+    }
+}
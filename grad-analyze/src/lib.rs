@@ -0,0 +1,7 @@
+//! Statistical analysis of arbitrary data.
+
+pub mod entropy;
+pub mod password;
+pub mod randomness;
+
+mod special;
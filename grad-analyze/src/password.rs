@@ -0,0 +1,828 @@
+//! Password strength estimation in the manner of zxcvbn.
+//!
+//! A password is split into the sequence of patterns which an attacker could guess in the fewest
+//! attempts: words from a dictionary, possibly capitalized, reversed or with l33t substitutions,
+//! keyboard patterns, repeats, sequences, dates, and characters which match no pattern and must be
+//! guessed by brute force. The number of guesses needed for the whole password is estimated from the
+//! guesses needed for each pattern.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+/// Only this many characters of a password are analyzed. The rest could only add to its strength.
+pub const MAX_LENGTH: usize = 100;
+
+/// The longest dictionary word which is searched for.
+const MAX_WORD_LENGTH: usize = 32;
+
+/// The number of guesses per character of a password which matches no pattern.
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+
+/// The fewest guesses attributed to a pattern which is part of a longer password.
+const MIN_GUESSES_SINGLE_CHAR: f64 = 10.0;
+const MIN_GUESSES_MULTI_CHAR: f64 = 50.0;
+
+/// Guesses added for every pattern beyond the first, so that a password is not overly split.
+const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: f64 = 10_000.0;
+
+/// The fewest years separating a date from the present.
+const MIN_YEAR_SPACE: f64 = 20.0;
+
+const DATE_SEPARATORS: &[char] = &[' ', '/', '\\', '_', '.', '-'];
+
+/// The most common passwords in leaked password databases, most common first.
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456", "password", "123456789", "12345678", "12345", "1234567", "qwerty", "1234567890", "111111",
+    "123123", "abc123", "password1", "1234", "iloveyou", "000000", "1q2w3e4r", "qwertyuiop", "654321",
+    "555555", "lovely", "7777777", "welcome", "888888", "princess", "dragon", "123qwe", "sunshine",
+    "666666", "football", "monkey", "letmein", "charlie", "aa123456", "donald", "qwerty123", "1q2w3e",
+    "master", "shadow", "baseball", "superman", "trustno1", "michael", "jessica", "ashley", "bailey",
+    "passw0rd", "hello", "freedom", "whatever", "qazwsx", "ninja", "mustang", "starwars", "access",
+    "flower", "login", "admin", "solo", "121212", "batman", "zaq1zaq1", "loveme", "jennifer", "hunter",
+    "buster", "soccer", "harley", "ranger", "daniel", "thomas", "robert", "jordan", "pepper", "michelle",
+    "andrew", "tigger", "summer", "secret", "computer", "internet", "cheese", "killer", "matrix",
+    "696969", "biteme", "maggie", "ginger", "george", "joshua", "cookie", "silver", "orange", "112233",
+    "987654321", "test", "changeme", "default", "root", "guest"
+];
+
+/// The characters which are commonly substituted for each letter.
+const L33T: &[(char, &[char])] = &[
+    ('4', &['a']), ('@', &['a']), ('8', &['b']), ('(', &['c']), ('{', &['c']), ('[', &['c']), ('<', &['c']),
+    ('3', &['e']), ('6', &['g']), ('9', &['g']), ('1', &['i', 'l']), ('!', &['i']), ('|', &['i', 'l']),
+    ('7', &['l', 't']), ('0', &['o']), ('$', &['s']), ('5', &['s']), ('+', &['t']), ('%', &['x']), ('2', &['z'])
+];
+
+/// The rows of a US QWERTY keyboard, unshifted and shifted, with the horizontal offset of each row
+/// in key widths.
+const QWERTY: &[(&str, &str, f64)] = &[
+    ("`1234567890-=", "~!@#$%^&*()_+", 0.0),
+    ("qwertyuiop[]\\", "QWERTYUIOP{}|", 1.5),
+    ("asdfghjkl;'", "ASDFGHJKL:\"", 1.75),
+    ("zxcvbnm,./", "ZXCVBNM<>?", 2.25)
+];
+
+/// A list of words, each of which is ranked by the number of guesses needed to find it.
+#[derive(Debug, Clone)]
+pub struct Dictionary {
+    name: String,
+    ranks: HashMap<String, u64>
+}
+
+impl Dictionary {
+    /// A dictionary whose words are listed from most to least common.
+    pub fn ranked<I, S>(name: impl Into<String>, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        let mut ranks = HashMap::new();
+
+        for word in words {
+            let rank = ranks.len() as u64 + 1;
+
+            ranks.entry(word.as_ref().to_lowercase()).or_insert(rank);
+        }
+
+        Dictionary { name: name.into(), ranks }
+    }
+
+    /// A dictionary whose words are equally likely, such as a word list for passphrases. Each word is
+    /// ranked by the size of the dictionary.
+    pub fn uniform<I, S>(name: impl Into<String>, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        let words: Vec<String> = words.into_iter().map(|word| word.as_ref().to_lowercase()).collect();
+        let rank = words.len() as u64;
+
+        Dictionary { name: name.into(), ranks: words.into_iter().map(|word| (word, rank)).collect() }
+    }
+
+    /// The most common passwords.
+    pub fn common_passwords() -> Self {
+        Dictionary::ranked("common passwords", COMMON_PASSWORDS)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.ranks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranks.is_empty()
+    }
+
+    fn rank(&self, word: &str) -> Option<u64> {
+        self.ranks.get(word).copied()
+    }
+}
+
+/// The kind of pattern matched by part of a password.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "pattern", rename_all = "snake_case")]
+pub enum Pattern {
+    /// A word from a dictionary.
+    Dictionary { word: String, dictionary: String, rank: u64, reversed: bool, l33t: bool, uppercase: bool },
+
+    /// Adjacent keys on a QWERTY keyboard, with the number of changes of direction and of shifted keys.
+    Spatial { turns: usize, shifted: usize },
+
+    /// A string repeated a number of times.
+    Repeat { base: String, count: usize },
+
+    /// Characters whose codes differ by a constant step, such as `abc` or `9753`.
+    Sequence { ascending: bool },
+
+    /// A date of the given year, with or without separators between its parts.
+    Date { year: i32, separator: bool },
+
+    /// A recent year.
+    Year { year: i32 },
+
+    /// Characters which match no pattern.
+    Bruteforce
+}
+
+/// A part of a password which matches a pattern.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Match {
+    /// The index of the first character of the match.
+    pub start: usize,
+
+    /// The index after the last character of the match.
+    pub end: usize,
+
+    pub token: String,
+    pub guesses: f64,
+
+    #[serde(flatten)]
+    pub pattern: Pattern
+}
+
+/// The time needed to guess a password in seconds, at the rates of several kinds of attack.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CrackTimes {
+    /// An online attack limited to 100 guesses per hour.
+    pub online_throttled: f64,
+
+    /// An online attack at 10 guesses per second.
+    pub online_unthrottled: f64,
+
+    /// An offline attack on a slow password hash, such as bcrypt, at 10,000 guesses per second.
+    pub offline_slow_hash: f64,
+
+    /// An offline attack on a fast hash, such as SHA-1, at 10 billion guesses per second.
+    pub offline_fast_hash: f64
+}
+
+impl CrackTimes {
+    fn new(guesses: f64) -> Self {
+        CrackTimes {
+            online_throttled: guesses / (100.0 / 3600.0),
+            online_unthrottled: guesses / 10.0,
+            offline_slow_hash: guesses / 1e4,
+            offline_fast_hash: guesses / 1e10
+        }
+    }
+}
+
+/// The estimated strength of a password.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Estimate {
+    /// The estimated number of guesses needed to find the password.
+    pub guesses: f64,
+
+    /// The base-2 logarithm of the number of guesses.
+    pub entropy: f64,
+
+    /// A score from 0, which is too guessable, to 4, which is very unguessable.
+    pub score: u8,
+
+    pub crack_times: CrackTimes,
+
+    /// The patterns into which the password was split.
+    pub sequence: Vec<Match>
+}
+
+/// Estimates the strength of passwords with a set of dictionaries.
+#[derive(Debug, Clone)]
+pub struct Estimator {
+    dictionaries: Vec<Dictionary>,
+    reference_year: i32
+}
+
+/// The best sequence of matches with a given number of matches which ends at a given character.
+#[derive(Debug, Clone)]
+struct State {
+    product: f64,
+    guesses: f64,
+    sequence: Vec<Match>
+}
+
+impl Estimator {
+    /// Create an estimator which finds words from `dictionaries` and judges dates against `reference_year`,
+    /// usually the current year.
+    pub fn new(dictionaries: Vec<Dictionary>, reference_year: i32) -> Self {
+        Estimator { dictionaries, reference_year }
+    }
+
+    /// Estimate the strength of the first [`MAX_LENGTH`] characters of `password`.
+    pub fn estimate(&self, password: &str) -> Estimate {
+        let chars: Vec<char> = password.chars().take(MAX_LENGTH).collect();
+        let (guesses, sequence) = self.most_guessable(&chars, &mut HashMap::new());
+
+        let score = match guesses {
+            guesses if guesses < 1e3 + 5.0 => 0,
+            guesses if guesses < 1e6 + 5.0 => 1,
+            guesses if guesses < 1e8 + 5.0 => 2,
+            guesses if guesses < 1e10 + 5.0 => 3,
+            _ => 4
+        };
+
+        Estimate {
+            guesses,
+            entropy: guesses.log2(),
+            score,
+            crack_times: CrackTimes::new(guesses),
+            sequence
+        }
+    }
+
+    /// Find the sequence of matches which covers `chars` in the fewest guesses, remembering the guesses
+    /// needed for the base of each repeat in `bases`.
+    fn most_guessable(&self, chars: &[char], bases: &mut HashMap<Vec<char>, f64>) -> (f64, Vec<Match>) {
+        let length = chars.len();
+
+        if length == 0 { return (1.0, Vec::new()) }
+
+        let mut matches = self.matches(chars, bases);
+
+        for candidate in &mut matches {
+            candidate.guesses = candidate.guesses.max(minimum_guesses(candidate, length));
+        }
+
+        let mut best: Vec<BTreeMap<usize, State>> = vec![BTreeMap::new(); length];
+
+        for end in 1..=length {
+            for candidate in matches.iter().filter(|candidate| candidate.end == end) {
+                extend(&mut best, candidate.clone());
+            }
+
+            for start in 0..end {
+                extend(&mut best, bruteforce(chars, start, end));
+            }
+        }
+
+        best[length - 1].values()
+            .min_by(|a, b| a.guesses.total_cmp(&b.guesses))
+            .map(|state| (state.guesses, state.sequence.clone()))
+            .unwrap_or((1.0, Vec::new()))
+    }
+
+    /// Find every match of every pattern other than brute force.
+    fn matches(&self, chars: &[char], bases: &mut HashMap<Vec<char>, f64>) -> Vec<Match> {
+        let mut matches = self.dictionary_matches(chars);
+
+        matches.extend(spatial_matches(chars));
+        matches.extend(self.repeat_matches(chars, bases));
+        matches.extend(sequence_matches(chars));
+        matches.extend(self.date_matches(chars));
+
+        matches
+    }
+
+    fn dictionary_matches(&self, chars: &[char]) -> Vec<Match> {
+        let lower: Vec<char> = chars.iter().map(|char| char.to_lowercase().next().unwrap_or(*char)).collect();
+        let mut matches = Vec::new();
+
+        for start in 0..chars.len() {
+            for end in start + 1..=chars.len().min(start + MAX_WORD_LENGTH) {
+                let token: String = chars[start..end].iter().collect();
+                let word: String = lower[start..end].iter().collect();
+                let reversed: String = word.chars().rev().collect();
+                let uppercase = uppercase_variations(&chars[start..end]);
+
+                for dictionary in &self.dictionaries {
+                    let mut found = |word: &str, rank: u64, reversed: bool, l33t: f64| {
+                        matches.push(Match {
+                            start,
+                            end,
+                            token: token.clone(),
+                            guesses: rank as f64 * uppercase * l33t * if reversed { 2.0 } else { 1.0 },
+                            pattern: Pattern::Dictionary {
+                                word: word.to_string(),
+                                dictionary: dictionary.name().to_string(),
+                                rank,
+                                reversed,
+                                l33t: l33t > 1.0,
+                                uppercase: uppercase > 1.0
+                            }
+                        })
+                    };
+
+                    if let Some(rank) = dictionary.rank(&word) { found(&word, rank, false, 1.0) }
+
+                    if end - start >= 3 && reversed != word && let Some(rank) = dictionary.rank(&reversed) {
+                        found(&reversed, rank, true, 1.0)
+                    }
+
+                    if end - start >= 3 {
+                        for (unsubstituted, substitutions) in unl33t(&lower[start..end]) {
+                            if let Some(rank) = dictionary.rank(&unsubstituted) {
+                                found(&unsubstituted, rank, false, l33t_variations(&lower[start..end], &substitutions))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+
+    fn repeat_matches(&self, chars: &[char], bases: &mut HashMap<Vec<char>, f64>) -> Vec<Match> {
+        let mut matches = Vec::new();
+
+        for start in 0..chars.len() {
+            for size in 1..=(chars.len() - start) / 2 {
+                let base = &chars[start..start + size];
+                let count = chars[start..].chunks_exact(size).take_while(|chunk| *chunk == base).count();
+
+                // A single character must repeat three times, and a longer string twice.
+                if count < 2 || (size == 1 && count < 3) { continue }
+
+                // Report only the longest repeat of each base, starting where the repeats begin.
+                if start >= size && chars[start - size..start] == *base { continue }
+
+                // The same bases recur in the repeats of every shorter base, so each is estimated once.
+                let base_guesses = match bases.get(base) {
+                    Some(&guesses) => guesses,
+                    None => {
+                        let (guesses, _) = self.most_guessable(base, bases);
+
+                        bases.insert(base.to_vec(), guesses);
+                        guesses
+                    }
+                };
+
+                matches.push(Match {
+                    start,
+                    end: start + size * count,
+                    token: chars[start..start + size * count].iter().collect(),
+                    guesses: base_guesses * count as f64,
+                    pattern: Pattern::Repeat { base: base.iter().collect(), count }
+                });
+            }
+        }
+
+        matches
+    }
+
+    fn date_matches(&self, chars: &[char]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let year_space = |year: i32| ((year - self.reference_year).abs() as f64).max(MIN_YEAR_SPACE);
+
+        for start in 0..chars.len() {
+            for end in start + 4..=chars.len().min(start + 10) {
+                let token: String = chars[start..end].iter().collect();
+
+                if let Some((year, separator)) = parse_date(&token) {
+                    let guesses = 365.0 * year_space(year) * if separator { 4.0 } else { 1.0 };
+
+                    matches.push(Match { start, end, token: token.clone(), guesses, pattern: Pattern::Date { year, separator } });
+                }
+
+                if end - start == 4 && let Ok(year @ 1900..=2050) = token.parse::<i32>() && token.chars().all(|char| char.is_ascii_digit()) {
+                    matches.push(Match { start, end, token, guesses: year_space(year), pattern: Pattern::Year { year } });
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+/// Extend the best sequences which end before `candidate` with it.
+fn extend(best: &mut [BTreeMap<usize, State>], candidate: Match) {
+    let previous: Vec<(usize, Option<State>)> = match candidate.start {
+        0 => vec![(0, None)],
+        start => best[start - 1].iter()
+            // Adjacent brute force matches are never better than one covering both.
+            .filter(|(_, state)| !(candidate.pattern == Pattern::Bruteforce &&
+                state.sequence.last().is_some_and(|last| last.pattern == Pattern::Bruteforce)))
+            .map(|(&count, state)| (count, Some(state.clone())))
+            .collect()
+    };
+
+    for (count, state) in previous {
+        let count = count + 1;
+        let product = state.as_ref().map_or(1.0, |state| state.product) * candidate.guesses;
+        let guesses = factorial(count) * product + MIN_GUESSES_BEFORE_GROWING_SEQUENCE.powi(count as i32 - 1);
+        let states = &mut best[candidate.end - 1];
+
+        // A sequence of more matches is kept only if it needs fewer guesses.
+        if states.iter().any(|(&other, state)| other <= count && state.guesses <= guesses) { continue }
+
+        let mut sequence = state.map(|state| state.sequence).unwrap_or_default();
+
+        sequence.push(candidate.clone());
+        states.insert(count, State { product, guesses, sequence });
+    }
+}
+
+fn bruteforce(chars: &[char], start: usize, end: usize) -> Match {
+    let length = end - start;
+    let minimum = if length == 1 { MIN_GUESSES_SINGLE_CHAR + 1.0 } else { MIN_GUESSES_MULTI_CHAR + 1.0 };
+
+    Match {
+        start,
+        end,
+        token: chars[start..end].iter().collect(),
+        guesses: BRUTEFORCE_CARDINALITY.powi(length as i32).clamp(minimum, f64::MAX),
+        pattern: Pattern::Bruteforce
+    }
+}
+
+fn minimum_guesses(candidate: &Match, length: usize) -> f64 {
+    match candidate.end - candidate.start {
+        size if size == length => 1.0,
+        1 => MIN_GUESSES_SINGLE_CHAR,
+        _ => MIN_GUESSES_MULTI_CHAR
+    }
+}
+
+fn factorial(n: usize) -> f64 {
+    (2..=n).map(|k| k as f64).product()
+}
+
+/// The number of ways to choose `k` of `n` items.
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k.min(n - k.min(n))).fold(1.0, |product, i| product * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// The number of guesses needed to find the capitalization of a word.
+fn uppercase_variations(chars: &[char]) -> f64 {
+    let upper = chars.iter().filter(|char| char.is_uppercase()).count();
+    let lower = chars.iter().filter(|char| char.is_lowercase()).count();
+
+    let first = chars.first().is_some_and(|char| char.is_uppercase());
+    let last = chars.last().is_some_and(|char| char.is_uppercase());
+
+    match (upper, lower) {
+        (0, _) => 1.0,
+        (_, 0) => 2.0,
+        (1, _) if first || last => 2.0,
+        (upper, lower) => (1..=upper.min(lower)).map(|i| binomial(upper + lower, i)).sum()
+    }
+}
+
+/// The number of guesses needed to find which letters of a word were substituted.
+fn l33t_variations(lower: &[char], substitutions: &[(char, char)]) -> f64 {
+    substitutions.iter()
+        .map(|&(substitute, letter)| {
+            let substituted = lower.iter().filter(|&&char| char == substitute).count();
+            let unsubstituted = lower.iter().filter(|&&char| char == letter).count();
+
+            match (substituted, unsubstituted) {
+                (0, _) | (_, 0) => 2.0,
+                (substituted, unsubstituted) => (1..=substituted.min(unsubstituted))
+                    .map(|i| binomial(substituted + unsubstituted, i))
+                    .sum()
+            }
+        })
+        .product()
+}
+
+/// Every reading of a lowercase token in which each l33t character consistently stands for one letter,
+/// together with the substitutions made.
+fn unl33t(lower: &[char]) -> Vec<(String, Vec<(char, char)>)> {
+    let mut substitutes: Vec<(char, &[char])> = L33T.iter()
+        .filter(|(substitute, _)| lower.contains(substitute))
+        .copied()
+        .collect();
+
+    substitutes.truncate(8);
+
+    if substitutes.is_empty() { return Vec::new() }
+
+    let mut readings: Vec<Vec<(char, char)>> = vec![Vec::new()];
+
+    for (substitute, letters) in substitutes {
+        readings = readings.into_iter()
+            .flat_map(|reading| letters.iter().map(move |&letter| {
+                let mut reading = reading.clone();
+
+                reading.push((substitute, letter));
+                reading
+            }))
+            .collect();
+    }
+
+    readings.into_iter()
+        .map(|substitutions| {
+            let word = lower.iter()
+                .map(|char| substitutions.iter().find(|(substitute, _)| substitute == char).map_or(*char, |&(_, letter)| letter))
+                .collect();
+
+            (word, substitutions)
+        })
+        .collect()
+}
+
+/// The row, horizontal position and shift state of a key on a QWERTY keyboard.
+fn key(char: char) -> Option<(usize, f64, bool)> {
+    QWERTY.iter().enumerate().find_map(|(row, (unshifted, shifted, offset))| {
+        unshifted.chars().position(|key| key == char).map(|column| (row, offset + column as f64, false))
+            .or_else(|| shifted.chars().position(|key| key == char).map(|column| (row, offset + column as f64, true)))
+    })
+}
+
+/// The direction from one key to an adjacent key, or [`None`] if the keys are not adjacent.
+fn direction(from: (usize, f64, bool), to: (usize, f64, bool)) -> Option<usize> {
+    let (from_row, from_x, _) = from;
+    let (to_row, to_x, _) = to;
+
+    match to_row as isize - from_row as isize {
+        0 if (to_x - from_x).abs() == 1.0 => Some(usize::from(to_x > from_x)),
+        rows @ (-1 | 1) if (to_x - from_x).abs() < 1.0 => Some(2 + usize::from(rows > 0) * 2 + usize::from(to_x > from_x)),
+        _ => None
+    }
+}
+
+/// The number of keys on a QWERTY keyboard, and the average number of keys adjacent to each.
+fn keyboard() -> (f64, f64) {
+    let keys: Vec<(usize, f64, bool)> = QWERTY.iter().flat_map(|(unshifted, ..)| unshifted.chars()).filter_map(key).collect();
+    let neighbours = keys.iter()
+        .map(|&from| keys.iter().filter(|&&to| direction(from, to).is_some()).count())
+        .sum::<usize>();
+
+    (2.0 * keys.len() as f64, neighbours as f64 / keys.len() as f64)
+}
+
+fn spatial_matches(chars: &[char]) -> Vec<Match> {
+    let (starting_positions, average_degree) = keyboard();
+    let mut matches = Vec::new();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let mut end = start + 1;
+        let mut turns = 0;
+        let mut last_direction = None;
+
+        while end < chars.len() {
+            let Some(direction) = key(chars[end - 1]).zip(key(chars[end])).and_then(|(from, to)| direction(from, to)) else { break };
+
+            if last_direction != Some(direction) { turns += 1 }
+
+            last_direction = Some(direction);
+            end += 1;
+        }
+
+        if end - start >= 3 {
+            let length = end - start;
+            let shifted = chars[start..end].iter().filter(|&&char| key(char).is_some_and(|(_, _, shifted)| shifted)).count();
+
+            let mut guesses: f64 = (2..=length)
+                .flat_map(|i| (1..=turns.min(i - 1)).map(move |j| (i, j)))
+                .map(|(i, j)| binomial(i - 1, j - 1) * starting_positions * average_degree.powi(j as i32))
+                .sum();
+
+            guesses *= match (shifted, length - shifted) {
+                (0, _) => 1.0,
+                (_, 0) => 2.0,
+                (shifted, unshifted) => (1..=shifted.min(unshifted)).map(|i| binomial(shifted + unshifted, i)).sum()
+            };
+
+            matches.push(Match {
+                start,
+                end,
+                token: chars[start..end].iter().collect(),
+                guesses,
+                pattern: Pattern::Spatial { turns, shifted }
+            });
+
+            start = end - 1;
+        }
+        else {
+            start += 1;
+        }
+    }
+
+    matches
+}
+
+fn sequence_matches(chars: &[char]) -> Vec<Match> {
+    let class = |char: char| match char {
+        'a'..='z' => Some(0),
+        'A'..='Z' => Some(1),
+        '0'..='9' => Some(2),
+        _ => None
+    };
+
+    let mut matches = Vec::new();
+    let mut start = 0;
+
+    while start + 2 < chars.len() {
+        let delta = chars[start + 1] as i64 - chars[start] as i64;
+
+        let same = |index: usize| chars[index] as i64 - chars[index - 1] as i64 == delta && class(chars[index]) == class(chars[start]);
+        let end = (start + 1..chars.len()).take_while(|&index| same(index)).last().map_or(start + 1, |index| index + 1);
+
+        if end - start >= 3 && (1..=5).contains(&delta.abs()) && class(chars[start]).is_some() {
+            let first = chars[start];
+            let base = match first {
+                'a' | 'A' | 'z' | 'Z' | '0' | '1' | '9' => 4.0,
+                '0'..='9' => 10.0,
+                _ => 26.0
+            };
+
+            matches.push(Match {
+                start,
+                end,
+                token: chars[start..end].iter().collect(),
+                guesses: base * if delta > 0 { 1.0 } else { 2.0 } * (end - start) as f64,
+                pattern: Pattern::Sequence { ascending: delta > 0 }
+            });
+
+            start = end - 1;
+        }
+        else {
+            start += 1;
+        }
+    }
+
+    matches
+}
+
+/// The year of a date written as digits, optionally with the same separator between its three parts,
+/// and whether it had separators.
+fn parse_date(token: &str) -> Option<(i32, bool)> {
+    let separator = token.chars().find(|char| DATE_SEPARATORS.contains(char));
+
+    let parts: Vec<&str> = match separator {
+        Some(separator) => token.split(separator).collect(),
+        None if (4..=8).contains(&token.len()) && token.chars().all(|char| char.is_ascii_digit()) => {
+            return (1..token.len() - 1)
+                .flat_map(|first| (first + 1..token.len()).map(move |second| (first, second)))
+                .find_map(|(first, second)| date_year(&token[..first], &token[first..second], &token[second..]))
+                .map(|year| (year, false))
+        }
+        None => return None
+    };
+
+    match parts.as_slice() {
+        [a, b, c] if parts.iter().all(|part| !part.is_empty() && part.chars().all(|char| char.is_ascii_digit())) => {
+            date_year(a, b, c).map(|year| (year, true))
+        }
+        _ => None
+    }
+}
+
+/// The year of a date in day-month-year, month-day-year, year-month-day or year-day-month order.
+fn date_year(a: &str, b: &str, c: &str) -> Option<i32> {
+    let year = |part: &str| match (part.len(), part.parse::<i32>().ok()?) {
+        (4, year @ 1000..=2050) => Some(year),
+        (2, year @ 50..=99) => Some(1900 + year),
+        (2, year) => Some(2000 + year),
+        _ => None
+    };
+
+    let day_month = |first: &str, second: &str| {
+        if first.len() > 2 || second.len() > 2 { return false }
+
+        match (first.parse::<u32>(), second.parse::<u32>()) {
+            (Ok(first), Ok(second)) => ((1..=31).contains(&first) && (1..=12).contains(&second)) ||
+                ((1..=12).contains(&first) && (1..=31).contains(&second)),
+            _ => false
+        }
+    };
+
+    if day_month(a, b) && let Some(year) = year(c) { return Some(year) }
+    if day_month(b, c) && let Some(year) = year(a) { return Some(year) }
+
+    None
+}
+
+/// A rough description of a duration in seconds, such as `3 hours` or `centuries`.
+pub fn humanize(seconds: f64) -> String {
+    const UNITS: &[(f64, &str)] = &[
+        (60.0 * 60.0 * 24.0 * 365.0, "year"),
+        (60.0 * 60.0 * 24.0 * 31.0, "month"),
+        (60.0 * 60.0 * 24.0, "day"),
+        (60.0 * 60.0, "hour"),
+        (60.0, "minute"),
+        (1.0, "second")
+    ];
+
+    if seconds < 1.0 { return String::from("less than a second") }
+    if seconds >= 100.0 * UNITS[0].0 { return String::from("centuries") }
+
+    let (size, unit) = UNITS.iter().find(|(size, _)| seconds >= *size).copied().unwrap_or(UNITS[5]);
+    let count = (seconds / size).round() as u64;
+
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dictionary, Estimator, Pattern};
+
+    fn estimator() -> Estimator {
+        let words = Dictionary::uniform("words", ["correct", "horse", "battery", "staple", "monkey"]);
+
+        Estimator::new(vec![Dictionary::common_passwords(), words], 2026)
+    }
+
+    fn patterns(password: &str) -> Vec<Pattern> {
+        estimator().estimate(password).sequence.into_iter().map(|candidate| candidate.pattern).collect()
+    }
+
+    #[test]
+    fn common_passwords_are_weak() {
+        let estimate = estimator().estimate("password");
+
+        assert_eq!(estimate.score, 0);
+        assert_eq!(estimate.guesses, 3.0);
+    }
+
+    #[test]
+    fn finds_l33t_and_capitalized_words() {
+        let estimate = estimator().estimate("P@ssw0rd");
+
+        assert_eq!(estimate.score, 0);
+        assert!(matches!(
+            &estimate.sequence[..],
+            [super::Match { pattern: Pattern::Dictionary { l33t: true, uppercase: true, .. }, .. }]
+        ));
+    }
+
+    #[test]
+    fn finds_reversed_words() {
+        assert!(matches!(&patterns("drowssap")[..], [Pattern::Dictionary { reversed: true, .. }]));
+    }
+
+    #[test]
+    fn finds_keyboard_patterns() {
+        assert!(matches!(&patterns("zxcvbnm")[..], [Pattern::Spatial { turns: 1, shifted: 0 }]));
+        assert!(matches!(&patterns("1qaz")[..], [Pattern::Spatial { .. }]));
+    }
+
+    #[test]
+    fn finds_repeats_and_sequences() {
+        assert!(matches!(&patterns("aaaaaa")[..], [Pattern::Repeat { count: 6, .. }]));
+        assert!(matches!(&patterns("horsehorsehorse")[..], [Pattern::Repeat { count: 3, .. }]));
+        assert!(matches!(&patterns("lmnopq")[..], [Pattern::Sequence { ascending: true }]));
+        assert!(matches!(&patterns("97531")[..], [Pattern::Sequence { ascending: false }]));
+    }
+
+    #[test]
+    fn long_repeats_are_estimated_quickly() {
+        let estimate = estimator().estimate(&"a".repeat(100));
+
+        assert!(matches!(&estimate.sequence[..], [super::Match { pattern: Pattern::Repeat { count: 100, .. }, .. }]));
+        assert!(matches!(&patterns(&"abc".repeat(33))[..], [Pattern::Repeat { count: 33, .. }]));
+    }
+
+    #[test]
+    fn finds_dates_and_years() {
+        assert!(matches!(&patterns("13/05/1997")[..], [Pattern::Date { year: 1997, separator: true }]));
+        assert!(matches!(&patterns("19970513")[..], [Pattern::Date { year: 1997, separator: false }]));
+        assert!(matches!(&patterns("monkey1987")[..], [Pattern::Dictionary { .. }, Pattern::Year { year: 1987 }]));
+    }
+
+    #[test]
+    fn passphrases_are_stronger_than_their_words() {
+        let estimator = estimator();
+        let passphrase = estimator.estimate("correcthorsebatterystaple");
+
+        assert_eq!(passphrase.sequence.len(), 4);
+        assert!(passphrase.guesses > estimator.estimate("correcthorse").guesses);
+    }
+
+    #[test]
+    fn random_passwords_are_strong() {
+        let estimate = estimator().estimate("Xk8#vQ2!mZ9pL4@w");
+
+        assert_eq!(estimate.score, 4);
+        assert!(estimate.entropy > 40.0);
+    }
+
+    #[test]
+    fn empty_passwords_need_one_guess() {
+        let estimate = estimator().estimate("");
+
+        assert_eq!(estimate.guesses, 1.0);
+        assert_eq!(estimate.score, 0);
+    }
+
+    #[test]
+    fn can_humanize_durations() {
+        assert_eq!(super::humanize(0.5), "less than a second");
+        assert_eq!(super::humanize(1.0), "1 second");
+        assert_eq!(super::humanize(7200.0), "2 hours");
+        assert_eq!(super::humanize(1e10), "centuries");
+    }
+}
@@ -0,0 +1,372 @@
+//! A subset of the statistical tests for random number generators of NIST SP 800-22.
+//!
+//! Each test computes one or more p-values: the probability that a truly random sequence would be
+//! at least as far from what is expected as the sequence tested. A sequence fails a test if any of
+//! its p-values is below the chosen significance level, so even a perfect generator fails each test
+//! in about one sequence in a hundred at the conventional level of 0.01.
+
+use std::f64::consts::{LN_2, SQRT_2};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::special::{erfc, igamc};
+
+/// The conventional significance level of the tests.
+pub const SIGNIFICANCE: f64 = 0.01;
+
+/// The fewest bits which can be tested.
+pub const MIN_LENGTH: usize = 128;
+
+/// The length of the blocks of the block frequency test.
+const BLOCK_FREQUENCY_SIZE: usize = 128;
+
+/// The longest templates of the serial and approximate entropy tests.
+const MAX_SERIAL_LENGTH: u32 = 16;
+const MAX_APPROXIMATE_ENTROPY_LENGTH: u32 = 10;
+
+#[derive(Debug, Error)]
+pub enum RandomnessError {
+    #[error("At least {required} bits are needed to test randomness, but only {length} were given.")]
+    TooShort { required: usize, length: usize }
+}
+
+/// A statistical test of randomness.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Test {
+    /// Whether there are as many ones as zeros.
+    Frequency,
+
+    /// Whether there are as many ones as zeros within each block.
+    BlockFrequency,
+
+    /// Whether runs of identical bits are as long as expected.
+    Runs,
+
+    /// Whether the longest run of ones within each block is as long as expected.
+    LongestRun,
+
+    /// Whether every pattern of a given length occurs equally often.
+    Serial,
+
+    /// Whether patterns of one length occur as often as expected from those one bit shorter.
+    ApproximateEntropy,
+
+    /// Whether the sums of the sequence, counting zeros as -1, stray as far from zero as expected,
+    /// both forwards and backwards.
+    CumulativeSums
+}
+
+impl Test {
+    pub fn name(self) -> &'static str {
+        match self {
+            Test::Frequency => "Frequency",
+            Test::BlockFrequency => "Block Frequency",
+            Test::Runs => "Runs",
+            Test::LongestRun => "Longest Run of Ones",
+            Test::Serial => "Serial",
+            Test::ApproximateEntropy => "Approximate Entropy",
+            Test::CumulativeSums => "Cumulative Sums"
+        }
+    }
+}
+
+/// The result of a test.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Outcome {
+    pub test: Test,
+
+    /// The length in bits of the blocks or templates of the test, if it has any.
+    pub parameter: Option<usize>,
+
+    pub p_values: Vec<f64>
+}
+
+impl Outcome {
+    /// Whether every p-value is at least `significance`.
+    pub fn passed(&self, significance: f64) -> bool {
+        self.p_values.iter().all(|&p_value| p_value >= significance)
+    }
+}
+
+/// The bits of `data`, most significant first.
+pub fn bits(data: &[u8]) -> Vec<bool> {
+    data.iter().flat_map(|byte| (0..8).rev().map(move |shift| byte >> shift & 1 == 1)).collect()
+}
+
+/// Run every test on `bits`, choosing their parameters according to its length.
+pub fn run(bits: &[bool]) -> Result<Vec<Outcome>, RandomnessError> {
+    let length = bits.len();
+
+    if length < MIN_LENGTH { return Err(RandomnessError::TooShort { required: MIN_LENGTH, length }) }
+
+    let log = length.ilog2();
+
+    Ok(vec![
+        frequency(bits),
+        block_frequency(bits, BLOCK_FREQUENCY_SIZE),
+        runs(bits),
+        longest_run(bits),
+        serial(bits, (log - 3).min(MAX_SERIAL_LENGTH) as usize),
+        approximate_entropy(bits, (log - 6).min(MAX_APPROXIMATE_ENTROPY_LENGTH) as usize),
+        cumulative_sums(bits)
+    ])
+}
+
+fn ones(bits: &[bool]) -> usize {
+    bits.iter().filter(|&&bit| bit).count()
+}
+
+/// The frequency (monobit) test.
+pub fn frequency(bits: &[bool]) -> Outcome {
+    let sum = 2.0 * ones(bits) as f64 - bits.len() as f64;
+    let statistic = sum.abs() / (bits.len() as f64).sqrt();
+
+    Outcome { test: Test::Frequency, parameter: None, p_values: vec![erfc(statistic / SQRT_2)] }
+}
+
+/// The frequency test within blocks of `size` bits. Bits after the last whole block are ignored.
+pub fn block_frequency(bits: &[bool], size: usize) -> Outcome {
+    let blocks = bits.chunks_exact(size);
+    let count = blocks.len() as f64;
+    let statistic = 4.0 * size as f64 * blocks
+        .map(|block| (ones(block) as f64 / size as f64 - 0.5).powi(2))
+        .sum::<f64>();
+
+    Outcome { test: Test::BlockFrequency, parameter: Some(size), p_values: vec![igamc(count / 2.0, statistic / 2.0)] }
+}
+
+/// The runs test.
+pub fn runs(bits: &[bool]) -> Outcome {
+    let length = bits.len() as f64;
+    let proportion = ones(bits) as f64 / length;
+
+    // The test is meaningless unless the frequency test would pass.
+    let p_value = if (proportion - 0.5).abs() >= 2.0 / length.sqrt() { 0.0 } else {
+        let runs = 1 + bits.windows(2).filter(|pair| pair[0] != pair[1]).count();
+        let expected = 2.0 * length * proportion * (1.0 - proportion);
+
+        erfc((runs as f64 - expected).abs() / (2.0 * (2.0 * length).sqrt() * proportion * (1.0 - proportion)))
+    };
+
+    Outcome { test: Test::Runs, parameter: None, p_values: vec![p_value] }
+}
+
+/// The test for the longest run of ones in a block, with blocks of 8, 128 or 10,000 bits depending on
+/// the length of the sequence.
+pub fn longest_run(bits: &[bool]) -> Outcome {
+    // The size of the blocks, the longest run counted in the first class, and the probability of each class.
+    let (size, shortest, probabilities): (usize, usize, &[f64]) = match bits.len() {
+        750_000.. => (10_000, 10, &[0.0882, 0.2092, 0.2483, 0.1933, 0.1208, 0.0675, 0.0727]),
+        6_272.. => (128, 4, &[0.1174, 0.2430, 0.2493, 0.1752, 0.1027, 0.1124]),
+        _ => (8, 1, &[0.2148, 0.3672, 0.2305, 0.1875])
+    };
+
+    let mut counts = vec![0u64; probabilities.len()];
+
+    for block in bits.chunks_exact(size) {
+        let longest = block.split(|&bit| !bit).map(<[bool]>::len).max().unwrap_or(0);
+
+        counts[longest.clamp(shortest, shortest + probabilities.len() - 1) - shortest] += 1;
+    }
+
+    let blocks = (bits.len() / size) as f64;
+    let statistic: f64 = counts.iter()
+        .zip(probabilities)
+        .map(|(&count, probability)| (count as f64 - blocks * probability).powi(2) / (blocks * probability))
+        .sum();
+
+    let freedom = (probabilities.len() - 1) as f64;
+
+    Outcome { test: Test::LongestRun, parameter: Some(size), p_values: vec![igamc(freedom / 2.0, statistic / 2.0)] }
+}
+
+/// The number of occurrences of each pattern of `length` bits, overlapping, with the sequence
+/// wrapped around so that every bit begins a pattern.
+fn pattern_counts(bits: &[bool], length: usize) -> Vec<u64> {
+    if length == 0 { return vec![bits.len() as u64] }
+
+    let mask = (1usize << length) - 1;
+    let mut counts = vec![0u64; 1 << length];
+    let mut pattern = 0;
+
+    for (index, &bit) in bits.iter().chain(&bits[..length - 1]).enumerate() {
+        pattern = (pattern << 1 | usize::from(bit)) & mask;
+
+        if index >= length - 1 { counts[pattern] += 1 }
+    }
+
+    counts
+}
+
+/// The counts of the patterns one bit shorter than those counted in `counts`, which are their prefixes.
+fn shorten(counts: &[u64]) -> Vec<u64> {
+    counts.chunks_exact(2).map(|pair| pair[0] + pair[1]).collect()
+}
+
+/// The serial test with templates of `length` bits, which must be at least two.
+pub fn serial(bits: &[bool], length: usize) -> Outcome {
+    let n = bits.len() as f64;
+    let psi = |counts: &[u64]| {
+        counts.len() as f64 / n * counts.iter().map(|&count| (count as f64).powi(2)).sum::<f64>() - n
+    };
+
+    let counts = pattern_counts(bits, length);
+    let shorter = shorten(&counts);
+    let shortest = shorten(&shorter);
+
+    let (psi, psi_shorter, psi_shortest) = (psi(&counts), psi(&shorter), psi(&shortest));
+    let first = psi - psi_shorter;
+    let second = psi - 2.0 * psi_shorter + psi_shortest;
+
+    Outcome {
+        test: Test::Serial,
+        parameter: Some(length),
+        p_values: vec![
+            igamc(2f64.powi(length as i32 - 2), first / 2.0),
+            igamc(2f64.powi(length as i32 - 3), second / 2.0)
+        ]
+    }
+}
+
+/// The approximate entropy test with templates of `length` and `length + 1` bits.
+pub fn approximate_entropy(bits: &[bool], length: usize) -> Outcome {
+    let n = bits.len() as f64;
+    let phi = |counts: &[u64]| {
+        counts.iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| count as f64 / n * (count as f64 / n).ln())
+            .sum::<f64>()
+    };
+
+    let longer = pattern_counts(bits, length + 1);
+    let entropy = phi(&shorten(&longer)) - phi(&longer);
+    let statistic = 2.0 * n * (LN_2 - entropy);
+
+    Outcome {
+        test: Test::ApproximateEntropy,
+        parameter: Some(length),
+        p_values: vec![igamc(2f64.powi(length as i32 - 1), statistic / 2.0)]
+    }
+}
+
+/// The cumulative sums test, forwards and backwards.
+pub fn cumulative_sums(bits: &[bool]) -> Outcome {
+    let excursion = |bits: &mut dyn Iterator<Item = &bool>| {
+        bits.scan(0i64, |sum, &bit| {
+            *sum += if bit { 1 } else { -1 };
+
+            Some(sum.unsigned_abs())
+        })
+        .max()
+        .unwrap_or(0)
+    };
+
+    let forward = excursion(&mut bits.iter());
+    let backward = excursion(&mut bits.iter().rev());
+
+    Outcome {
+        test: Test::CumulativeSums,
+        parameter: None,
+        p_values: vec![cumulative_sums_p_value(bits.len(), forward), cumulative_sums_p_value(bits.len(), backward)]
+    }
+}
+
+/// The probability that a random walk of `length` steps strays at least `excursion` from zero.
+fn cumulative_sums_p_value(length: usize, excursion: u64) -> f64 {
+    if excursion == 0 { return 1.0 }
+
+    let (n, z) = (length as f64, excursion as f64);
+    let normal = |x: f64| 0.5 * erfc(-x / SQRT_2);
+    let term = |k: i64, a: i64, b: i64| normal((4 * k + a) as f64 * z / n.sqrt()) - normal((4 * k + b) as f64 * z / n.sqrt());
+
+    // The bounds are truncated towards zero, as in the reference implementation.
+    let upper = ((n / z - 1.0) / 4.0) as i64;
+    let first: f64 = ((((-n / z) + 1.0) / 4.0) as i64..=upper).map(|k| term(k, 1, -1)).sum();
+    let second: f64 = ((((-n / z) - 3.0) / 4.0) as i64..=upper).map(|k| term(k, 3, 1)).sum();
+
+    (1.0 - first + second).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Outcome, RandomnessError, SIGNIFICANCE, Test};
+
+    fn parse(bits: &str) -> Vec<bool> {
+        bits.chars().map(|char| char == '1').collect()
+    }
+
+    fn assert_p_values(outcome: Outcome, expected: &[f64]) {
+        assert_eq!(outcome.p_values.len(), expected.len());
+
+        for (actual, expected) in outcome.p_values.iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-6, "{}: {} is not close to {}", outcome.test.name(), actual, expected);
+        }
+    }
+
+    // The examples of NIST SP 800-22 revision 1a.
+
+    #[test]
+    fn can_run_frequency_test() {
+        assert_p_values(super::frequency(&parse("1011010101")), &[0.527089]);
+    }
+
+    #[test]
+    fn can_run_block_frequency_test() {
+        assert_p_values(super::block_frequency(&parse("0110011010"), 3), &[0.801252]);
+    }
+
+    #[test]
+    fn can_run_runs_test() {
+        assert_p_values(super::runs(&parse("1001101011")), &[0.147232]);
+    }
+
+    #[test]
+    fn can_run_longest_run_test() {
+        let bits = parse(concat!(
+            "11001100000101010110110001001100111000000000001001001101010100010001001111010110100000001101011111",
+            "001100111001101101100010110010"
+        ));
+
+        assert_p_values(super::longest_run(&bits), &[0.180598]);
+    }
+
+    #[test]
+    fn can_run_serial_test() {
+        assert_p_values(super::serial(&parse("0011011101"), 3), &[0.808792, 0.670320]);
+    }
+
+    #[test]
+    fn can_run_approximate_entropy_test() {
+        assert_p_values(super::approximate_entropy(&parse("0100110101"), 3), &[0.261961]);
+    }
+
+    #[test]
+    fn can_run_cumulative_sums_test() {
+        let outcome = super::cumulative_sums(&parse("1011010111"));
+
+        assert!((outcome.p_values[0] - 0.411_658_8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn expands_bytes_most_significant_bit_first() {
+        assert_eq!(super::bits(&[0b1000_0001, 0b0100_0000]), parse("1000000101000000"));
+    }
+
+    #[test]
+    fn rejects_short_sequences() {
+        assert!(matches!(super::run(&[true; 127]), Err(RandomnessError::TooShort { required: 128, length: 127 })));
+    }
+
+    #[test]
+    fn fails_patterned_sequences() {
+        let bits = super::bits(&[0b0101_0101; 4096]);
+        let outcomes = super::run(&bits).unwrap();
+
+        assert_eq!(outcomes.len(), 7);
+        assert!(outcomes.iter().all(|outcome| outcome.p_values.iter().all(|p_value| (0.0..=1.0).contains(p_value))));
+        assert!(!outcomes.iter().find(|outcome| outcome.test == Test::Runs).unwrap().passed(SIGNIFICANCE));
+        assert!(!outcomes.iter().find(|outcome| outcome.test == Test::Serial).unwrap().passed(SIGNIFICANCE));
+    }
+}
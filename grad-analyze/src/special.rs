@@ -0,0 +1,126 @@
+//! Special functions for computing the p-values of statistical tests.
+
+/// The relative precision at which series and continued fractions are truncated.
+const EPSILON: f64 = 1e-15;
+
+/// A number near the smallest representable, used to avoid division by zero in continued fractions.
+const TINY: f64 = 1e-300;
+
+const MAX_ITERATIONS: usize = 10_000;
+
+/// The coefficients of the Lanczos approximation with g = 7 and n = 9.
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7
+];
+
+/// The natural logarithm of the gamma function of a positive number.
+pub fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        // The reflection formula.
+        return (std::f64::consts::PI / (std::f64::consts::PI * x).sin()).ln() - ln_gamma(1.0 - x)
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = LANCZOS[1..].iter()
+        .enumerate()
+        .fold(LANCZOS[0], |sum, (index, coefficient)| sum + coefficient / (x + index as f64 + 1.0));
+
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// The upper regularized incomplete gamma function, Q(a, x) = Γ(a, x) / Γ(a).
+pub fn igamc(a: f64, x: f64) -> f64 {
+    if x <= 0.0 || a <= 0.0 { return 1.0 }
+
+    let prefactor = (-x + a * x.ln() - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        // The series for the lower function converges quickly here.
+        let mut term = 1.0 / a;
+        let mut sum = term;
+
+        for n in 1..MAX_ITERATIONS {
+            term *= x / (a + n as f64);
+            sum += term;
+
+            if term.abs() < sum.abs() * EPSILON { break }
+        }
+
+        (1.0 - sum * prefactor).max(0.0)
+    }
+    else {
+        // The continued fraction for the upper function, evaluated with the modified Lentz method.
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut fraction = d;
+
+        for n in 1..MAX_ITERATIONS {
+            let an = -(n as f64) * (n as f64 - a);
+
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY { d = TINY }
+            c = b + an / c;
+            if c.abs() < TINY { c = TINY }
+            d = 1.0 / d;
+
+            let delta = d * c;
+
+            fraction *= delta;
+
+            if (delta - 1.0).abs() < EPSILON { break }
+        }
+
+        (prefactor * fraction).min(1.0)
+    }
+}
+
+/// The probability that a chi-square variable with `freedom` degrees of freedom exceeds `statistic`.
+pub fn chi_square_upper(statistic: f64, freedom: f64) -> f64 {
+    igamc(freedom / 2.0, statistic / 2.0)
+}
+
+/// The complementary error function.
+pub fn erfc(x: f64) -> f64 {
+    // erfc(x) = Q(1/2, x²) for non-negative x, and erfc(-x) = 2 - erfc(x).
+    if x >= 0.0 { igamc(0.5, x * x) } else { 2.0 - igamc(0.5, x * x) }
+}
+
+#[cfg(test)]
+mod tests {
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} is not close to {}", actual, expected);
+    }
+
+    #[test]
+    fn can_compute_ln_gamma() {
+        assert_close(super::ln_gamma(1.0), 0.0);
+        assert_close(super::ln_gamma(5.0), 24f64.ln());
+        assert_close(super::ln_gamma(0.5), std::f64::consts::PI.sqrt().ln());
+    }
+
+    #[test]
+    fn can_compute_erfc() {
+        assert_close(super::erfc(0.0), 1.0);
+        assert_close(super::erfc(1.0), 0.157_299_207_050_285_13);
+        assert_close(super::erfc(-1.0), 1.842_700_792_949_715);
+    }
+
+    #[test]
+    fn can_compute_chi_square_p_values() {
+        // With two degrees of freedom the upper tail is exp(-x / 2).
+        assert_close(super::chi_square_upper(3.0, 2.0), (-1.5f64).exp());
+        // The median of the distribution with 255 degrees of freedom is about 254.33.
+        assert!((super::chi_square_upper(254.33, 255.0) - 0.5).abs() < 1e-3);
+    }
+}
@@ -1,7 +1,22 @@
+use std::path::PathBuf;
+
 use clap::{Args, Subcommand, ValueEnum};
 use either::Either;
 
+use grad_hash::Algorithm;
+use grad_hash::mac::MacAlgorithm;
+use grad_parse::chars::CharSpec;
+use grad_parse::escape::Escaped;
+use grad_parse::key::Key;
+use grad_parse::namespace::Namespace;
 use grad_parse::range::{Range, RangeInclusive};
+use grad_parse::seed::Seed;
+use grad_parse::size::Size;
+use grad_parse::template::Template;
+use grad_parse::timestamp::Timestamp;
+use grad_rand::passphrase::BuiltinList;
+use grad_rand::rng::Backend;
+use grad_rand::uuid::UuidFormat;
 
 mod parse;
 
@@ -15,19 +30,240 @@ pub enum Command {
         command: CreateCommand
     },
 
+    /// Convert data from one format to another.
+    Convert {
+        /// The sub-command to execute.
+        #[command(subcommand)]
+        command: ConvertCommand
+    },
+
+    /// Work with dates, times and time zones.
+    Time {
+        /// The sub-command to execute.
+        #[command(subcommand)]
+        command: TimeCommand
+    },
+
     /// Generate random data with the specified properties.
     Random {
+        /// Seed the random number generator to produce reproducible output. Acceptable formats are:
+        ///
+        /// 1. Sixty-four hexadecimal digits, optionally prefixed with 0x.
+        ///
+        /// 2. An unsigned 64-bit integer.
+        ///
+        /// 3. Any other string, which is treated as a passphrase.
+        #[arg(long = "seed", global = true)]
+        seed: Option<Seed>,
+
+        /// The random number generator used to produce output.
+        #[arg(long = "rng", value_enum, global = true, default_value_t = Backend::ChaCha20)]
+        rng: Backend,
+
+        /// Draw random bytes verbatim from a file instead of a generator. Use - for standard input.
+        #[arg(long = "rng-file", global = true, conflicts_with_all = ["seed", "rng"])]
+        rng_file: Option<PathBuf>,
+
+        /// The sub-command to execute.
+        #[command(subcommand)]
+        command: Box<RandomCommand>
+    },
+
+    /// Encode data from a file or standard input as text.
+    Encode {
+        /// The encoding to apply.
+        #[arg(value_enum)]
+        encoding: Encoding,
+
+        /// The file to encode. Use - or omit it for standard input.
+        input: Option<PathBuf>,
+
+        /// The human-readable part of a Bech32 or Bech32m string, such as bc or age.
+        #[arg(long = "hrp", required_if_eq_any = [("encoding", "bech32"), ("encoding", "bech32m")])]
+        hrp: Option<String>,
+
+        /// Do not write a newline after the encoded data.
+        #[arg(short = 'n', long = "no-newline")]
+        no_newline: bool
+    },
+
+    /// Decode text from a file or standard input and write the data to standard output.
+    Decode {
+        /// The encoding to reverse.
+        #[arg(value_enum)]
+        encoding: Encoding,
+
+        /// The file to decode. Use - or omit it for standard input.
+        input: Option<PathBuf>,
+
+        /// The expected human-readable part of a Bech32 or Bech32m string. Any part is accepted if omitted.
+        #[arg(long = "hrp")]
+        hrp: Option<String>,
+
+        /// Ignore whitespace, missing or superfluous padding, and case where the alphabet allows it.
+        /// Percent-encoding and quoted-printable also accept lowercase escapes and pass invalid
+        /// escapes through verbatim.
+        #[arg(short = 'l', long = "lenient")]
+        lenient: bool
+    },
+
+    /// Analyze the statistical properties of data.
+    Analyze {
         /// The sub-command to execute.
         #[command(subcommand)]
-        command: RandomCommand
+        command: AnalyzeCommand
+    },
+
+    /// Hash files or standard input, or verify the checksums listed in files.
+    Hash {
+        /// The files to hash, or with `--check`, the checksum files to verify. Use - or omit them for standard input.
+        files: Vec<PathBuf>,
+
+        /// The hash function.
+        #[arg(short = 'a', long = "algorithm", value_enum, default_value_t = Algorithm::Sha256)]
+        algorithm: Algorithm,
+
+        /// The encoding of each digest.
+        #[arg(short = 'e', long = "encoding", value_enum, default_value_t = Encoding::Hex)]
+        encoding: Encoding,
+
+        /// The human-readable part of digests encoded with Bech32 or Bech32m.
+        #[arg(long = "hrp", required_if_eq_any = [("encoding", "bech32"), ("encoding", "bech32m")])]
+        hrp: Option<String>,
+
+        /// Read checksum files in the format written by this command or by sha256sum, and verify
+        /// the checksum of every file they list.
+        #[arg(short = 'c', long = "check")]
+        check: bool
+    },
+
+    /// Compute a message authentication code of files or standard input, or verify one.
+    Mac {
+        /// The files to authenticate. Use - or omit them for standard input.
+        files: Vec<PathBuf>,
+
+        /// The secret key. Acceptable formats are:
+        ///
+        /// 1. Hexadecimal digits, optionally prefixed with 0x.
+        ///
+        /// 2. Base64 with the standard or URL-safe alphabet.
+        ///
+        /// 3. @ followed by the path of a file whose bytes are the key.
+        ///
+        /// 4. random: followed by a number of bytes, to generate a new key and print it in
+        ///    hexadecimal to standard error.
+        #[arg(short = 'k', long = "key")]
+        key: Key,
+
+        /// The message authentication code.
+        #[arg(short = 'a', long = "algorithm", visible_alias = "alg", value_enum, default_value_t = MacAlgorithm::HmacSha256)]
+        algorithm: MacAlgorithm,
+
+        /// The encoding of each code.
+        #[arg(short = 'e', long = "encoding", value_enum, default_value_t = Encoding::Hex)]
+        encoding: Encoding,
+
+        /// The human-readable part of codes encoded with Bech32 or Bech32m.
+        #[arg(long = "hrp", required_if_eq_any = [("encoding", "bech32"), ("encoding", "bech32m")])]
+        hrp: Option<String>,
+
+        /// Verify that the code of a single input is this code, in the chosen encoding, instead of
+        /// printing it. Codes are compared in constant time.
+        #[arg(long = "verify")]
+        verify: Option<String>
     }
 }
 
-#[derive(ValueEnum, Debug, Clone)]
-pub enum TimestampFormat {
-    ISO8601,
-    RFC2822,
-    RFC3339
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Encoding {
+    /// The data itself, unencoded.
+    Raw,
+
+    /// Lowercase hexadecimal.
+    Hex,
+
+    /// Uppercase hexadecimal.
+    HexUpper,
+
+    /// Base32 with the RFC 4648 alphabet and padding.
+    Base32,
+
+    /// Base32 with the RFC 4648 alphabet, without padding.
+    Base32Nopad,
+
+    /// Base32 with the RFC 4648 extended hex alphabet and padding.
+    Base32hex,
+
+    /// Base32 with the RFC 4648 extended hex alphabet, without padding.
+    Base32hexNopad,
+
+    /// Crockford's base32, without padding.
+    Crockford,
+
+    /// Base58 with the Bitcoin alphabet.
+    Base58,
+
+    /// Base58 with the Flickr alphabet.
+    Base58Flickr,
+
+    /// Base64 with the RFC 4648 alphabet and padding.
+    Base64,
+
+    /// Base64 with the RFC 4648 alphabet, without padding.
+    Base64Nopad,
+
+    /// Base64 with the RFC 4648 URL-safe alphabet and padding.
+    Base64url,
+
+    /// Base64 with the RFC 4648 URL-safe alphabet, without padding.
+    Base64urlNopad,
+
+    /// Ascii85, as produced by btoa.
+    #[value(alias = "base85")]
+    Ascii85,
+
+    /// Z85, as defined by ZeroMQ. The number of bytes must be a multiple of four.
+    Z85,
+
+    /// Bech32, as defined by BIP 173. Requires `--hrp`.
+    Bech32,
+
+    /// Bech32m, as defined by BIP 350. Requires `--hrp`.
+    Bech32m,
+
+    /// Percent-encoding, as defined by RFC 3986, of every byte other than the unreserved characters.
+    Percent,
+
+    /// Quoted-printable, as defined by RFC 2045.
+    QuotedPrintable
+}
+
+impl Encoding {
+    /// The library encoding, with the human-readable part used by the Bech32 encodings.
+    pub fn with_hrp(self, hrp: Option<String>) -> grad_encoding::Encoding {
+        match self {
+            Encoding::Raw => grad_encoding::Encoding::Raw,
+            Encoding::Hex => grad_encoding::Encoding::Hex,
+            Encoding::HexUpper => grad_encoding::Encoding::HexUpper,
+            Encoding::Base32 => grad_encoding::Encoding::Base32,
+            Encoding::Base32Nopad => grad_encoding::Encoding::Base32NoPad,
+            Encoding::Base32hex => grad_encoding::Encoding::Base32Hex,
+            Encoding::Base32hexNopad => grad_encoding::Encoding::Base32HexNoPad,
+            Encoding::Crockford => grad_encoding::Encoding::Crockford,
+            Encoding::Base58 => grad_encoding::Encoding::Base58,
+            Encoding::Base58Flickr => grad_encoding::Encoding::Base58Flickr,
+            Encoding::Base64 => grad_encoding::Encoding::Base64,
+            Encoding::Base64Nopad => grad_encoding::Encoding::Base64NoPad,
+            Encoding::Base64url => grad_encoding::Encoding::Base64Url,
+            Encoding::Base64urlNopad => grad_encoding::Encoding::Base64UrlNoPad,
+            Encoding::Ascii85 => grad_encoding::Encoding::Ascii85,
+            Encoding::Z85 => grad_encoding::Encoding::Z85,
+            Encoding::Bech32 => grad_encoding::Encoding::Bech32(hrp.unwrap_or_default()),
+            Encoding::Bech32m => grad_encoding::Encoding::Bech32m(hrp.unwrap_or_default()),
+            Encoding::Percent => grad_encoding::Encoding::Percent,
+            Encoding::QuotedPrintable => grad_encoding::Encoding::QuotedPrintable
+        }
+    }
 }
 
 #[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -42,7 +278,10 @@ pub enum CharacterSet {
     Digit,
 
     /// Include characters from `[!@*-_.]`.
-    Symbol
+    Symbol,
+
+    /// Include every printable ASCII symbol, `[!-/:-@[-`{-~]`.
+    Punctuation
 }
 
 const LOWERCASE: &[char; 26] = &[
@@ -61,13 +300,22 @@ const DIGIT: &[char; 10] = &[
 
 const SYMBOL: &[char; 6] = &['!', '@', '*', '-', '_', '.'];
 
+const PUNCTUATION: &[char; 32] = &[
+    '!', '"', '#', '$', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/', ':',
+    ';', '<', '=', '>', '?', '@', '[', '\\', ']', '^', '_', '`', '{', '|', '}', '~'
+];
+
+/// Characters which are easily mistaken for one another, removed by `--no-ambiguous`.
+pub const AMBIGUOUS: &[char; 6] = &['0', 'O', '1', 'l', 'I', '|'];
+
 impl From<CharacterSet> for Vec<char> {
     fn from(value: CharacterSet) -> Self {
         match value {
             CharacterSet::LowerCase => Vec::from(LOWERCASE),
             CharacterSet::UpperCase => Vec::from(UPPERCASE),
             CharacterSet::Digit => Vec::from(DIGIT),
-            CharacterSet::Symbol => Vec::from(SYMBOL)
+            CharacterSet::Symbol => Vec::from(SYMBOL),
+            CharacterSet::Punctuation => Vec::from(PUNCTUATION)
         }
     }
 }
@@ -78,42 +326,370 @@ impl From<&CharacterSet> for Vec<char> {
             CharacterSet::LowerCase => Vec::from(LOWERCASE),
             CharacterSet::UpperCase => Vec::from(UPPERCASE),
             CharacterSet::Digit => Vec::from(DIGIT),
-            CharacterSet::Symbol => Vec::from(SYMBOL)
+            CharacterSet::Symbol => Vec::from(SYMBOL),
+            CharacterSet::Punctuation => Vec::from(PUNCTUATION)
         }
     }
 }
 
+#[derive(Subcommand, Debug, Clone)]
+pub enum AnalyzeCommand {
+    /// Report the entropy and byte distribution of a file, as the ent program does.
+    Entropy {
+        /// The file to analyze. Use - or omit it for standard input.
+        input: Option<PathBuf>,
+
+        /// Analyze windows of this many bytes, optionally followed by a unit such as KiB, to find
+        /// encrypted or compressed regions.
+        #[arg(short = 'w', long = "window")]
+        window: Option<Size>,
+
+        /// The distance between the starts of successive windows. Defaults to the window size.
+        #[arg(long = "step", requires = "window")]
+        step: Option<Size>,
+
+        /// Print the number of occurrences of each byte value.
+        #[arg(long = "histogram", conflicts_with = "window")]
+        histogram: bool,
+
+        /// Print the report as JSON.
+        #[arg(long = "json")]
+        json: bool
+    },
+    /// Estimate the strength of passwords, read one per line, by the patterns an attacker would
+    /// guess them with: dictionary words, keyboard patterns, repeats, sequences and dates.
+    Password {
+        /// The file of passwords to analyze. Use - or omit it for standard input.
+        input: Option<PathBuf>,
+
+        /// Match words from a file instead of the built-in word list. The file may contain plain
+        /// text or zstd-compressed text, with one word per line, optionally in the diceware format.
+        #[arg(short = 'w', long = "wordlist", conflicts_with = "list")]
+        wordlist: Option<PathBuf>,
+
+        /// Match words from one of the built-in word lists instead of the default word list.
+        #[arg(short = 'l', long = "list", value_enum)]
+        list: Option<BuiltinList>,
+
+        /// Print the estimates as JSON.
+        #[arg(long = "json")]
+        json: bool
+    },
+    /// Run statistical tests of randomness from NIST SP 800-22 on the bits of a file: frequency,
+    /// block frequency, runs, longest run, serial, approximate entropy and cumulative sums.
+    Randomness {
+        /// The file to test. Use - or omit it for standard input.
+        input: Option<PathBuf>,
+
+        /// The p-value below which a test fails.
+        #[arg(long = "significance", default_value_t = 0.01)]
+        significance: f64,
+
+        /// Print the results as JSON.
+        #[arg(long = "json")]
+        json: bool
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConvertCommand {
+    /// Parse a timestamp and write it in another format. The format of the timestamp is detected
+    /// unless it is given: integers are read as Unix timestamps in the unit suggested by their
+    /// magnitude, and timestamps without an offset are read in the zone given by --from-tz.
+    Timestamp {
+        /// The timestamp to convert. Timestamps are read from standard input, one per line, if it is omitted.
+        input: Option<String>,
+
+        /// The format of the timestamp.
+        #[arg(long = "from", value_enum)]
+        from: Option<grad_time::parse::Format>,
+
+        /// The time zone of timestamps without an offset: UTC, local, or an IANA time zone such as
+        /// Europe/Paris. Defaults to UTC.
+        #[arg(long = "from-tz")]
+        from_tz: Option<String>,
+
+        /// Write the timestamp in this time zone: UTC, local, or an IANA time zone such as
+        /// America/New_York. The timestamp keeps its own offset if this is omitted.
+        #[arg(long = "tz")]
+        tz: Option<String>,
+
+        /// The format in which to write the timestamp: iso8601, iso8601-basic, rfc2822, rfc3339, unix,
+        /// unix-ms, unix-us, unix-ns, or strftime: followed by a pattern. Defaults to RFC 3339.
+        #[arg(short = 'f', long = "format", value_parser = parse::parse_timestamp_format)]
+        format: Option<grad_time::Format>,
+
+        /// Truncate the timestamp to this precision, and write exactly as many fractional digits.
+        #[arg(long = "precision", value_enum)]
+        precision: Option<grad_time::Precision>
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum TimeCommand {
+    /// List the time zones of the IANA time zone database with their current offsets, and whether
+    /// daylight saving time is in effect.
+    Zones {
+        /// List only the zones whose names contain this text, ignoring case.
+        filter: Option<String>
+    },
+
+    /// Add a duration to a timestamp.
+    Add {
+        #[command(flatten)]
+        arithmetic: TimeArithmetic
+    },
+
+    /// Subtract a duration from a timestamp.
+    Sub {
+        #[command(flatten)]
+        arithmetic: TimeArithmetic
+    },
+
+    /// Print the time elapsed from one timestamp to another, in words unless a unit is given.
+    Diff {
+        /// The earlier timestamp, in any format accepted by convert timestamp, or now.
+        start: String,
+
+        /// The later timestamp, in any format accepted by convert timestamp, or now.
+        end: String,
+
+        /// The time zone of timestamps without an offset, and of now: UTC, local, or an IANA time
+        /// zone. Defaults to UTC, and to the local time zone for now.
+        #[arg(long = "tz")]
+        tz: Option<String>,
+
+        /// Print the difference as a number of this unit. Days and weeks are exactly 24 hours and 7 days.
+        #[arg(short = 'u', long = "unit", value_enum, conflicts_with = "iso8601")]
+        unit: Option<grad_time::duration::Unit>,
+
+        /// Print the difference as an ISO 8601 duration, such as PT36H.
+        #[arg(long = "iso8601")]
+        iso8601: bool
+    }
+}
+
+/// A timestamp and a duration by which to move it.
+#[derive(Args, Debug, Clone)]
+pub struct TimeArithmetic {
+    /// The timestamp, in any format accepted by convert timestamp, or now.
+    pub timestamp: String,
+
+    /// An ISO 8601 duration such as P1DT2H, or numbers and units such as 90m or 2w3d. The units
+    /// are y, mo, w, d, h, m, s, ms, us and ns.
+    #[arg(allow_hyphen_values = true)]
+    pub duration: String,
+
+    /// The time zone in which to add months and days, read timestamps without an offset, and
+    /// write the result: UTC, local, or an IANA time zone such as America/New_York. Timestamps
+    /// keep their own offset if this is omitted, and now is in the local time zone.
+    #[arg(long = "tz")]
+    pub tz: Option<String>,
+
+    /// The format in which to write the result, as for convert timestamp. Defaults to RFC 3339.
+    #[arg(short = 'f', long = "format", value_parser = parse::parse_timestamp_format)]
+    pub format: Option<grad_time::Format>,
+
+    /// Truncate the result to this precision, and write exactly as many fractional digits.
+    #[arg(long = "precision", value_enum)]
+    pub precision: Option<grad_time::Precision>
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum CreateCommand {
     /// Get the current time in the specified format.
+    #[command(args_conflicts_with_subcommands = true)]
     Timestamp {
         #[command(subcommand)]
         command: Option<TimestampCommand>,
 
-        /// The format to use when creating the timestamp.
-        #[arg(value_enum, short, long, global = true)]
-        format: Option<TimestampFormat>
+        /// The format to use when creating the timestamp: iso8601, iso8601-basic, rfc2822, rfc3339,
+        /// unix, unix-ms, unix-us, unix-ns, or strftime: followed by a pattern such as %Y-%m-%d.
+        #[arg(short, long, global = true, value_parser = parse::parse_timestamp_format)]
+        format: Option<grad_time::Format>,
+
+        /// Truncate the timestamp to this precision, and write exactly as many fractional digits.
+        #[arg(long = "precision", value_enum, global = true)]
+        precision: Option<grad_time::Precision>,
+
+        /// Create the timestamp in an IANA time zone, such as America/New_York, instead of the local
+        /// time zone.
+        #[arg(long = "tz")]
+        tz: Option<String>
     },
+    /// Create a UUID. Random (version 4) UUIDs are created unless another version is given.
+    Uuid {
+        /// The version of the UUIDs to create.
+        #[command(flatten)]
+        version: UuidVersionFlags,
+
+        /// The namespace of a name-based UUID: dns, url, oid, x500, or any UUID.
+        #[arg(long = "namespace", requires = "v5")]
+        namespace: Option<Namespace>,
+
+        /// The name of a name-based UUID.
+        #[arg(long = "name", requires = "v5")]
+        name: Option<String>,
+
+        /// Options for the timestamp of time-ordered UUIDs.
+        #[command(flatten)]
+        clock: Clock,
+
+        /// Options for formatting UUIDs.
+        #[command(flatten)]
+        output: UuidOutput,
+
+        /// Options for batch processing.
+        #[command(flatten)]
+        batch: Batch
+    }
+}
+
+/// Selects the version of a UUID to create. At most one version may be given.
+#[derive(Args, Debug, Copy, Clone)]
+#[group(multiple = false)]
+pub struct UuidVersionFlags {
+    /// Create a random UUID (version 4). This is the default.
+    #[arg(long = "v4")]
+    pub v4: bool,
+
+    /// Create a name-based UUID (version 5) from the SHA-1 hash of a namespace and a name. The
+    /// same namespace and name always produce the same UUID, so it cannot be batched.
+    #[arg(long = "v5", requires_all = ["namespace", "name"], conflicts_with = "iterations")]
+    pub v5: bool,
+
+    /// Create a time-ordered UUID (version 7). UUIDs in a batch share a timestamp and are
+    /// ordered by a counter, so they sort in the order in which they were requested.
+    #[arg(long = "v7")]
+    pub v7: bool
+}
+
+impl UuidVersionFlags {
+    /// The version selected by the flags.
+    pub fn version(self) -> UuidVersion {
+        match self {
+            UuidVersionFlags { v5: true, .. } => UuidVersion::V5,
+            UuidVersionFlags { v7: true, .. } => UuidVersion::V7,
+            _ => UuidVersion::V4
+        }
+    }
+}
+
+/// The versions of UUID which may be created.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UuidVersion {
+    V4,
+    V5,
+    V7
+}
+
+/// Clock configuration for time-based identifiers.
+#[derive(Args, Debug, Clone)]
+pub struct Clock {
+    /// Use this instant instead of the current time, for example to backfill data. Acceptable
+    /// formats are an RFC 3339 timestamp, or a Unix timestamp in milliseconds or, suffixed with s,
+    /// in seconds.
+    #[arg(long = "timestamp")]
+    pub timestamp: Option<Timestamp>
+}
+
+/// UUID formatting configuration.
+#[derive(Args, Debug, Clone)]
+pub struct UuidOutput {
+    /// The textual form of each UUID.
+    #[arg(short = 'f', long = "format", value_enum, default_value_t = UuidFormat::Hyphenated)]
+    pub format: UuidFormat,
+
+    /// Print hexadecimal digits in uppercase.
+    #[arg(short = 'u', long = "uppercase")]
+    pub uppercase: bool
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DelimiterMode {
+    /// Write the delimiter between outputs, but not after the final output.
+    Separator,
+
+    /// Write the delimiter after every output, including the final output.
+    Terminator
 }
 
-/// Log verbosity configuration.
+/// Batch processing configuration.
 #[derive(Args, Debug, Clone)]
 #[group()]
 pub struct Batch {
     /// The humber of batch operations to perform.
     pub iterations: Option<usize>,
 
-    /// The sequence used to separate batched outputs.
-    #[arg(short = 'D', long = "delimiter", default_value_t = String::from("\n"), requires = "iterations")]
-    pub delimiter: String
+    /// The sequence used to separate batched outputs. Escape sequences such as \t and \0 are interpreted.
+    #[arg(short = 'D', long = "delimiter", default_value = r"\n", requires = "iterations", conflicts_with = "null")]
+    pub delimiter: Escaped,
+
+    /// Separate batched outputs with NUL bytes, for use with `xargs -0`.
+    #[arg(short = '0', long = "null", requires = "iterations")]
+    pub null: bool,
+
+    /// Whether the delimiter separates outputs or terminates each of them.
+    #[arg(long = "delimiter-mode", value_enum, default_value_t = DelimiterMode::Separator, requires = "iterations")]
+    pub mode: DelimiterMode,
+
+    /// Write batched outputs in the order in which they were requested. Enabled by default for seeded output.
+    #[arg(long = "ordered", requires = "iterations", conflicts_with = "unordered")]
+    pub ordered: bool,
+
+    /// Write batched outputs as soon as they are generated, in no particular order.
+    #[arg(long = "unordered", requires = "iterations")]
+    pub unordered: bool
+}
+
+impl Batch {
+    /// The sequence written between batched outputs.
+    pub fn delimiter(&self) -> &[u8] {
+        if self.null { b"\0" } else { &self.delimiter }
+    }
+
+    /// Whether batched outputs should be written in order, given whether they are deterministic.
+    pub fn is_ordered(&self, deterministic: bool) -> bool {
+        self.ordered || (deterministic && ! self.unordered)
+    }
+}
+
+/// Entropy reporting configuration.
+#[derive(Args, Debug, Clone)]
+pub struct Entropy {
+    /// Print the entropy of each output, in bits, to standard error.
+    #[arg(long = "show-entropy")]
+    pub show: bool,
+
+    /// Ensure that each output carries at least this many bits of entropy, increasing the length as
+    /// needed. The length may be omitted, in which case the shortest sufficient length is used.
+    #[arg(long = "min-entropy", value_name = "BITS", value_parser = parse::parse_entropy)]
+    pub min_entropy: Option<f64>
 }
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum RandomCommand {
     /// Generate random bytes.
+    #[command(visible_alias = "bytes")]
     Byte {
-        /// The number of bytes to generate.
-        length: usize
+        /// The number of bytes to generate, optionally followed by a unit such as KiB, MB or G.
+        length: Size,
+
+        /// Write the bytes to a file instead of standard output.
+        #[arg(short = 'o', long = "output", conflicts_with_all = ["encoding", "iterations"])]
+        output: Option<PathBuf>,
+
+        /// Encode the bytes as text instead of writing them verbatim.
+        #[arg(short = 'e', long = "encoding", value_enum)]
+        encoding: Option<Encoding>,
+
+        /// The human-readable part of a Bech32 or Bech32m string, such as bc or age.
+        #[arg(long = "hrp", required_if_eq_any = [("encoding", "bech32"), ("encoding", "bech32m")])]
+        hrp: Option<String>,
+
+        /// Options for batch processing.
+        #[command(flatten)]
+        batch: Batch
     },
     /// Generate random bytes and encode them as a hexadecimal string.
     Hex {
@@ -122,7 +698,12 @@ pub enum RandomCommand {
         uppercase: bool,
 
         /// The length of the hexadecimal string in bytes.
-        length: usize,
+        #[arg(required_unless_present = "min_entropy")]
+        length: Option<usize>,
+
+        /// Options for entropy reporting.
+        #[command(flatten)]
+        entropy: Entropy,
 
         /// Options for batch processing.
         #[command(flatten)]
@@ -135,7 +716,12 @@ pub enum RandomCommand {
         url: bool,
 
         /// The length of the Base64 string in bytes.
-        length: usize,
+        #[arg(required_unless_present = "min_entropy")]
+        length: Option<usize>,
+
+        /// Options for entropy reporting.
+        #[command(flatten)]
+        entropy: Entropy,
 
         /// Options for batch processing.
         #[command(flatten)]
@@ -144,7 +730,12 @@ pub enum RandomCommand {
     /// Generate a random sequence of digits.
     Digit {
         /// The number of digits to generate.
-        length: usize,
+        #[arg(required_unless_present = "min_entropy")]
+        length: Option<usize>,
+
+        /// Options for entropy reporting.
+        #[command(flatten)]
+        entropy: Entropy,
 
         /// Options for batch processing.
         #[command(flatten)]
@@ -166,12 +757,37 @@ pub enum RandomCommand {
     },
     /// Generate a random password with a configurable character set.
     Password {
-        /// Which characters to include.
-        #[arg(short = 'i', long = "include", value_enum, default_values_t = vec![CharacterSet::LowerCase, CharacterSet::UpperCase, CharacterSet::Digit])]
+        /// Which characters to include. Defaults to lower-case, upper-case and digit unless `--chars`
+        /// is given.
+        #[arg(short = 'i', long = "include", value_enum)]
         include: Vec<CharacterSet>,
 
+        /// Include the characters of a specification, in which two characters joined by a hyphen
+        /// stand for every character between them (e.g. a-zA-Z0-9#%).
+        #[arg(short = 'c', long = "chars", value_name = "SPEC")]
+        chars: Vec<CharSpec>,
+
+        /// Exclude the characters of a specification, in the same form as `--chars`.
+        #[arg(short = 'x', long = "exclude", value_name = "SPEC")]
+        exclude: Vec<CharSpec>,
+
+        /// Exclude characters which are easily mistaken for one another, `0O1lI|`.
+        #[arg(long = "no-ambiguous")]
+        no_ambiguous: bool,
+
+        /// Require at least this many characters from a character set, in the form of class=count
+        /// (e.g. digit=2). Passwords which do not satisfy every minimum are discarded and drawn again,
+        /// which reduces the entropy of the password.
+        #[arg(long = "min", value_name = "CLASS=COUNT", value_parser = parse::parse_minimum)]
+        minimums: Vec<(CharacterSet, usize)>,
+
         /// The length of the password.
-        length: usize,
+        #[arg(required_unless_present = "min_entropy")]
+        length: Option<usize>,
+
+        /// Options for entropy reporting.
+        #[command(flatten)]
+        entropy: Entropy,
 
         /// Options for batch processing.
         #[command(flatten)]
@@ -183,8 +799,107 @@ pub enum RandomCommand {
         #[arg(short = 'S', long = "separator", default_value = " ")]
         separator: String,
 
+        /// Read words from a file instead of the built-in word list. The file may contain plain
+        /// text or zstd-compressed text, with one word per line, optionally in the diceware format.
+        #[arg(short = 'w', long = "wordlist", conflicts_with = "list")]
+        wordlist: Option<PathBuf>,
+
+        /// Choose words from one of the built-in word lists instead of the default word list.
+        #[arg(short = 'l', long = "list", value_enum)]
+        list: Option<BuiltinList>,
+
         /// The number of words to generate
-        length: usize,
+        #[arg(required_unless_present = "min_entropy")]
+        length: Option<usize>,
+
+        /// Options for entropy reporting.
+        #[command(flatten)]
+        entropy: Entropy,
+
+        /// Options for batch processing.
+        #[command(flatten)]
+        batch: Batch
+    },
+    /// Generate a random (version 4) UUID.
+    Uuid {
+        /// Options for formatting UUIDs.
+        #[command(flatten)]
+        output: UuidOutput,
+
+        /// Options for batch processing.
+        #[command(flatten)]
+        batch: Batch
+    },
+    /// Generate a ULID: a 48-bit timestamp and 80 random bits in Crockford's base32. ULIDs in a batch
+    /// share a timestamp and increase in the order in which they were requested.
+    Ulid {
+        /// Options for the timestamp of each ULID.
+        #[command(flatten)]
+        clock: Clock,
+
+        /// Options for batch processing.
+        #[command(flatten)]
+        batch: Batch
+    },
+    /// Generate a KSUID: a 32-bit timestamp and a 128-bit random payload in base62. KSUIDs in a batch
+    /// share a timestamp and each has an independent payload, so they sort in no particular order.
+    Ksuid {
+        /// Options for the timestamp of each KSUID.
+        #[command(flatten)]
+        clock: Clock,
+
+        /// Options for batch processing.
+        #[command(flatten)]
+        batch: Batch
+    },
+    /// Generate a NanoID: a string of symbols chosen uniformly from an alphabet.
+    Nanoid {
+        /// The alphabet, in the same form as the `--chars` option of the password command.
+        #[arg(short = 'a', long = "alphabet", default_value = "A-Za-z0-9_-")]
+        alphabet: CharSpec,
+
+        /// The length of the NanoID. Defaults to 21 unless `--min-entropy` is given.
+        length: Option<usize>,
+
+        /// Options for entropy reporting.
+        #[command(flatten)]
+        entropy: Entropy,
+
+        /// Options for batch processing.
+        #[command(flatten)]
+        batch: Batch
+    },
+    /// Generate a Snowflake ID: a 41-bit timestamp, a 10-bit worker number and a 12-bit sequence
+    /// number. IDs in a batch share a timestamp and increase in the order in which they were requested.
+    Snowflake {
+        /// The epoch from which the timestamp is counted, in the same form as `--timestamp`.
+        /// Defaults to the epoch of Twitter's Snowflake IDs.
+        #[arg(long = "epoch", default_value = "1288834974657")]
+        epoch: Timestamp,
+
+        /// The worker number, from 0 to 1023.
+        #[arg(short = 'w', long = "worker", default_value_t = 0)]
+        worker: u16,
+
+        /// Options for the timestamp of each ID.
+        #[command(flatten)]
+        clock: Clock,
+
+        /// Options for batch processing.
+        #[command(flatten)]
+        batch: Batch
+    },
+    /// Generate a pronounceable password from a template of consonant-vowel syllables, for
+    /// credentials which must be read aloud.
+    Pronounceable {
+        /// The slots of the password, in which C stands for a consonant, V for a vowel, D for a digit
+        /// and S for a symbol. With `--min-entropy`, the template is repeated as many times as needed.
+        #[arg(short = 'T', long = "template", default_value = "CVCCVC")]
+        template: Template,
+
+        /// Options for entropy reporting.
+        #[command(flatten)]
+        entropy: Entropy,
 
         /// Options for batch processing.
         #[command(flatten)]
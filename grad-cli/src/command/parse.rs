@@ -1,9 +1,14 @@
+use std::num::{ParseFloatError, ParseIntError};
 use std::str::FromStr;
 
+use clap::ValueEnum;
 use either::Either;
 use thiserror::Error;
 
 use grad_parse::range::{ParseRangeError, Range, RangeInclusive};
+use grad_time::{Format, FormatError};
+
+use super::CharacterSet;
 
 #[derive(Debug, Error)]
 #[error("The following errors occurred during parsing:\n\t{0}\n\t{1}")]
@@ -16,3 +21,120 @@ pub fn parse_range(value: &str) -> Result<Either<Range<i128>, RangeInclusive<i12
         (Err(left), Err(right)) => Err(ParseError(left, right)),
     }
 }
+
+#[derive(Debug, Error)]
+pub enum ParseMinimumError {
+    #[error("Expected a minimum in the form of class=count (e.g. digit=2).")]
+    NoMatch,
+
+    #[error("Unknown character set {0}.")]
+    CharacterSet(String),
+
+    #[error(transparent)]
+    ParseInt(#[from] ParseIntError)
+}
+
+pub fn parse_minimum(value: &str) -> Result<(CharacterSet, usize), ParseMinimumError> {
+    let (set, count) = value.split_once('=').ok_or(ParseMinimumError::NoMatch)?;
+    let set = CharacterSet::from_str(set.trim(), true).map_err(ParseMinimumError::CharacterSet)?;
+
+    Ok((set, count.trim().parse()?))
+}
+
+#[derive(Debug, Error)]
+pub enum ParseEntropyError {
+    #[error("The entropy must be a finite number of bits greater than zero.")]
+    OutOfRange,
+
+    #[error(transparent)]
+    ParseFloat(#[from] ParseFloatError)
+}
+
+/// Parse a minimum entropy, in bits, which must be finite and positive.
+pub fn parse_entropy(value: &str) -> Result<f64, ParseEntropyError> {
+    let bits: f64 = value.trim().parse()?;
+
+    if bits.is_finite() && bits > 0.0 { Ok(bits) } else { Err(ParseEntropyError::OutOfRange) }
+}
+
+#[derive(Debug, Error)]
+pub enum ParseTimestampFormatError {
+    #[error("Expected iso8601, iso8601-basic, rfc2822, rfc3339, unix, unix-ms, unix-us, unix-ns or strftime:<pattern>.")]
+    NoMatch,
+
+    #[error("The strftime pattern is empty.")]
+    EmptyPattern,
+
+    #[error(transparent)]
+    Pattern(#[from] FormatError)
+}
+
+/// Parse the name of a timestamp format, or `strftime:` followed by a valid pattern.
+pub fn parse_timestamp_format(value: &str) -> Result<Format, ParseTimestampFormatError> {
+    if let Some(pattern) = value.strip_prefix("strftime:") {
+        if pattern.is_empty() { return Err(ParseTimestampFormatError::EmptyPattern) }
+
+        return Ok(Format::strftime(pattern)?)
+    }
+
+    match value.to_ascii_lowercase().as_str() {
+        "iso8601" | "iso8601-extended" => Ok(Format::Iso8601),
+        "iso8601-basic" => Ok(Format::Iso8601Basic),
+        "rfc2822" => Ok(Format::Rfc2822),
+        "rfc3339" => Ok(Format::Rfc3339),
+        "unix" | "unix-s" => Ok(Format::Unix),
+        "unix-ms" => Ok(Format::UnixMillis),
+        "unix-us" => Ok(Format::UnixMicros),
+        "unix-ns" => Ok(Format::UnixNanos),
+        _ => Err(ParseTimestampFormatError::NoMatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CharacterSet, ParseEntropyError, ParseMinimumError, ParseTimestampFormatError};
+    use grad_time::Format;
+
+    #[test]
+    fn can_parse_minimum() {
+        assert_eq!(super::parse_minimum("lower-case=2").unwrap(), (CharacterSet::LowerCase, 2));
+        assert_eq!(super::parse_minimum("symbol=1").unwrap(), (CharacterSet::Symbol, 1));
+    }
+
+    #[test]
+    fn rejects_invalid_minimum() {
+        assert!(matches!(super::parse_minimum("digit"), Err(ParseMinimumError::NoMatch)));
+        assert!(matches!(super::parse_minimum("emoji=1"), Err(ParseMinimumError::CharacterSet(..))));
+        assert!(matches!(super::parse_minimum("digit=-1"), Err(ParseMinimumError::ParseInt(..))));
+    }
+
+    #[test]
+    fn can_parse_entropy() {
+        assert_eq!(super::parse_entropy("128").unwrap(), 128.0);
+        assert_eq!(super::parse_entropy("0.5").unwrap(), 0.5);
+    }
+
+    #[test]
+    fn rejects_invalid_entropy() {
+        for value in ["inf", "-inf", "NaN", "0", "-8"] {
+            assert!(matches!(super::parse_entropy(value), Err(ParseEntropyError::OutOfRange)), "{}", value);
+        }
+
+        assert!(matches!(super::parse_entropy("lots"), Err(ParseEntropyError::ParseFloat(..))));
+    }
+
+    #[test]
+    fn can_parse_timestamp_format() {
+        assert_eq!(super::parse_timestamp_format("ISO8601").unwrap(), Format::Iso8601);
+        assert_eq!(super::parse_timestamp_format("iso8601-basic").unwrap(), Format::Iso8601Basic);
+        assert_eq!(super::parse_timestamp_format("unix-ms").unwrap(), Format::UnixMillis);
+        assert_eq!(super::parse_timestamp_format("strftime:%H:%M").unwrap(), Format::Strftime(String::from("%H:%M")));
+    }
+
+    #[test]
+    fn rejects_invalid_timestamp_format() {
+        assert!(matches!(super::parse_timestamp_format("unix-days"), Err(ParseTimestampFormatError::NoMatch)));
+        assert!(matches!(super::parse_timestamp_format("strftime:"), Err(ParseTimestampFormatError::EmptyPattern)));
+        assert!(matches!(super::parse_timestamp_format("strftime:%Y-%Q"), Err(ParseTimestampFormatError::Pattern(..))));
+    }
+}
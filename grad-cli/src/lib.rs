@@ -21,3 +21,48 @@ pub struct Arguments {
 pub fn parse() -> Result<Arguments, Error> {
     Arguments::try_parse()
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use clap::error::ErrorKind;
+
+    use super::Arguments;
+    use super::command::{Command, CreateCommand, UuidVersion};
+
+    fn uuid_version(args: &[&str]) -> UuidVersion {
+        match Arguments::try_parse_from(args).unwrap().command {
+            Command::Create { command: CreateCommand::Uuid { version, .. } } => version.version(),
+            command => panic!("Unexpected command {:?}", command)
+        }
+    }
+
+    #[test]
+    fn selects_uuid_version() {
+        assert_eq!(uuid_version(&["grad", "create", "uuid"]), UuidVersion::V4);
+        assert_eq!(uuid_version(&["grad", "create", "uuid", "--v4"]), UuidVersion::V4);
+        assert_eq!(uuid_version(&["grad", "create", "uuid", "--v7", "4"]), UuidVersion::V7);
+        assert_eq!(uuid_version(&["grad", "create", "uuid", "--v5", "--namespace", "dns", "--name", "example.com"]), UuidVersion::V5);
+    }
+
+    #[test]
+    fn rejects_conflicting_uuid_options() {
+        let error = Arguments::try_parse_from(["grad", "create", "uuid", "--v4", "--v7"]).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
+
+        let error = Arguments::try_parse_from(["grad", "create", "uuid", "--v5", "--namespace", "dns", "--name", "example.com", "3"]).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn rejects_time_zone_with_timestamp_subcommands() {
+        for args in [["grad", "create", "timestamp", "--tz", "Europe/Paris", "utc"], ["grad", "create", "timestamp", "local", "--tz", "Europe/Paris"]] {
+            assert!(Arguments::try_parse_from(args).is_err(), "{:?}", args);
+        }
+
+        assert!(Arguments::try_parse_from(["grad", "create", "timestamp", "--tz", "Europe/Paris", "--format", "unix"]).is_ok());
+        assert!(Arguments::try_parse_from(["grad", "create", "timestamp", "utc", "--format", "unix", "--precision", "ms"]).is_ok());
+    }
+}
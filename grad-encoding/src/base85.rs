@@ -0,0 +1,168 @@
+//! The Ascii85 and Z85 encodings, which represent every four bytes as five symbols.
+
+use crate::DecodeError;
+
+/// The alphabet of Z85, as defined by ZeroMQ RFC 32.
+const Z85: &[u8; 85] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// The five base85 digits of a big-endian 32-bit group, most significant first.
+fn digits(group: u32) -> [u8; 5] {
+    let mut digits = [0u8; 5];
+    let mut value = group;
+
+    for digit in digits.iter_mut().rev() {
+        *digit = (value % 85) as u8;
+        value /= 85;
+    }
+
+    digits
+}
+
+/// The big-endian 32-bit group of five base85 digits, or [`None`] if it exceeds 32 bits.
+fn group(digits: &[u8; 5]) -> Option<u32> {
+    digits.iter().try_fold(0u32, |value, &digit| value.checked_mul(85)?.checked_add(digit as u32))
+}
+
+/// Encode `data` as Ascii85, without the `<~` and `~>` delimiters used by Adobe.
+///
+/// Groups of four zero bytes are abbreviated to `z`. A final partial group of `n` bytes is padded
+/// with zeros and truncated to `n + 1` symbols.
+pub fn encode_ascii85(data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(data.len().div_ceil(4) * 5);
+
+    for chunk in data.chunks(4) {
+        let mut group = [0u8; 4];
+
+        group[..chunk.len()].copy_from_slice(chunk);
+
+        let value = u32::from_be_bytes(group);
+
+        if value == 0 && chunk.len() == 4 {
+            output.push(b'z');
+
+            continue;
+        }
+
+        output.extend(digits(value)[..chunk.len() + 1].iter().map(|digit| digit + b'!'));
+    }
+
+    output
+}
+
+/// Decode Ascii85 without delimiters.
+pub fn decode_ascii85(input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let mut output = Vec::with_capacity(input.len() / 5 * 4 + 4);
+    let mut buffer = [0u8; 5];
+    let mut length = 0;
+    let mut start = 0;
+
+    for (offset, &byte) in input.iter().enumerate() {
+        match byte {
+            b'z' if length == 0 => {
+                output.extend_from_slice(&[0; 4]);
+
+                continue;
+            }
+            b'!'..=b'u' => {
+                if length == 0 { start = offset }
+
+                buffer[length] = byte - b'!';
+                length += 1;
+            }
+            _ => return Err(DecodeError::Symbol { offset, byte })
+        }
+
+        if length == 5 {
+            output.extend_from_slice(&group(&buffer).ok_or(DecodeError::Group { offset: start })?.to_be_bytes());
+            length = 0;
+        }
+    }
+
+    match length {
+        0 => (),
+        1 => return Err(DecodeError::Length { offset: input.len() }),
+        _ => {
+            // A partial group of n symbols was padded with the largest digit and encodes n - 1 bytes.
+            buffer[length..].fill(84);
+
+            let value = group(&buffer).ok_or(DecodeError::Group { offset: start })?;
+
+            output.extend_from_slice(&value.to_be_bytes()[..length - 1]);
+        }
+    }
+
+    Ok(output)
+}
+
+/// Encode `data` as Z85, which is defined only for inputs whose length is a multiple of four.
+///
+/// Returns [`None`] for any other input.
+pub fn encode_z85(data: &[u8]) -> Option<Vec<u8>> {
+    if !data.len().is_multiple_of(4) { return None }
+
+    let output = data.chunks(4)
+        .flat_map(|chunk| digits(u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])))
+        .map(|digit| Z85[digit as usize])
+        .collect();
+
+    Some(output)
+}
+
+/// Decode Z85, whose length must be a multiple of five.
+pub fn decode_z85(input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let mut output = Vec::with_capacity(input.len() / 5 * 4);
+
+    for (index, chunk) in input.chunks(5).enumerate() {
+        let start = index * 5;
+
+        if chunk.len() < 5 { return Err(DecodeError::Length { offset: start }) }
+
+        let mut buffer = [0u8; 5];
+
+        for (position, (&byte, digit)) in chunk.iter().zip(buffer.iter_mut()).enumerate() {
+            *digit = Z85.iter().position(|&symbol| symbol == byte)
+                .ok_or(DecodeError::Symbol { offset: start + position, byte })? as u8;
+        }
+
+        output.extend_from_slice(&group(&buffer).ok_or(DecodeError::Group { offset: start })?.to_be_bytes());
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DecodeError;
+
+    #[test]
+    fn can_encode_ascii85() {
+        assert_eq!(super::encode_ascii85(b"Hello, World!"), b"87cURD_*#4DfTZ)+T");
+        assert_eq!(super::encode_ascii85(b"\0\0\0\0abc"), b"z@:E^");
+        assert_eq!(super::encode_ascii85(b""), b"");
+    }
+
+    #[test]
+    fn can_decode_ascii85() {
+        assert_eq!(super::decode_ascii85(b"87cURD_*#4DfTZ)+T").unwrap(), b"Hello, World!");
+        assert_eq!(super::decode_ascii85(b"z@:E^").unwrap(), b"\0\0\0\0abc");
+        assert!(matches!(super::decode_ascii85(b"87cU~"), Err(DecodeError::Symbol { offset: 4, byte: b'~' })));
+        assert!(matches!(super::decode_ascii85(b"87cURD"), Err(DecodeError::Length { offset: 6 })));
+        assert!(matches!(super::decode_ascii85(b"uuuuu"), Err(DecodeError::Group { offset: 0 })));
+    }
+
+    #[test]
+    fn can_encode_z85() {
+        // The example given in ZeroMQ RFC 32.
+        let data = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+
+        assert_eq!(super::encode_z85(&data).unwrap(), b"HelloWorld");
+        assert_eq!(super::encode_z85(b"abc"), None);
+    }
+
+    #[test]
+    fn can_decode_z85() {
+        assert_eq!(super::decode_z85(b"HelloWorld").unwrap(), [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b]);
+        assert!(matches!(super::decode_z85(b"Hello~orld"), Err(DecodeError::Symbol { offset: 5, byte: b'~' })));
+        assert!(matches!(super::decode_z85(b"HelloWor"), Err(DecodeError::Length { offset: 5 })));
+    }
+}
@@ -0,0 +1,14 @@
+//! Hexadecimal digits shared by the escaping encodings.
+
+/// The uppercase hexadecimal digits.
+pub(crate) const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+/// The value of a hexadecimal digit, accepting lowercase digits only if `lowercase` is set.
+pub(crate) fn hex_value(byte: u8, lowercase: bool) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        b'a'..=b'f' if lowercase => Some(byte - b'a' + 10),
+        _ => None
+    }
+}
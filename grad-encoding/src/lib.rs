@@ -0,0 +1,477 @@
+//! Binary-to-text encodings.
+
+use std::sync::LazyLock;
+
+use bech32::primitives::decode::{CheckedHrpstring, CheckedHrpstringError};
+use bech32::{Bech32, Bech32m, Checksum, Hrp};
+use data_encoding::{
+    BASE32, BASE32_NOPAD, BASE32HEX, BASE32HEX_NOPAD, BASE64, BASE64_NOPAD, BASE64URL, BASE64URL_NOPAD, HEXLOWER,
+    HEXUPPER, DecodeKind, Specification
+};
+use thiserror::Error;
+
+mod base85;
+mod hex;
+mod percent;
+mod quoted_printable;
+
+pub use base85::{decode_ascii85, decode_z85, encode_ascii85, encode_z85};
+pub use percent::{decode_percent, encode_percent};
+pub use quoted_printable::{decode_quoted_printable, encode_quoted_printable};
+
+/// Crockford's base32, which omits `I`, `L`, `O` and `U` to avoid confusion, without padding.
+static CROCKFORD: LazyLock<data_encoding::Encoding> = LazyLock::new(|| {
+    let mut specification = Specification::new();
+
+    specification.symbols.push_str("0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+    specification.encoding().unwrap()
+});
+
+#[derive(Debug, Error)]
+pub enum DecodeError {
+    #[error("Invalid character '{}' at byte offset {offset}.", .byte.escape_ascii())]
+    Symbol { offset: usize, byte: u8 },
+
+    #[error("Invalid padding at byte offset {offset}.")]
+    Padding { offset: usize },
+
+    #[error("Non-zero trailing bits at byte offset {offset}.")]
+    Trailing { offset: usize },
+
+    #[error("Incomplete input at byte offset {offset}.")]
+    Length { offset: usize },
+
+    #[error("The group at byte offset {offset} exceeds 32 bits.")]
+    Group { offset: usize },
+
+    #[error("Expected the human-readable part {expected:?}, but found {found:?}.")]
+    Hrp { expected: String, found: String },
+
+    #[error(transparent)]
+    Bech32(#[from] CheckedHrpstringError)
+}
+
+impl DecodeError {
+    /// Translate the offset of the error with `map`.
+    fn map_offset(self, map: impl Fn(usize) -> usize) -> Self {
+        match self {
+            DecodeError::Symbol { offset, byte } => DecodeError::Symbol { offset: map(offset), byte },
+            DecodeError::Padding { offset } => DecodeError::Padding { offset: map(offset) },
+            DecodeError::Trailing { offset } => DecodeError::Trailing { offset: map(offset) },
+            DecodeError::Length { offset } => DecodeError::Length { offset: map(offset) },
+            DecodeError::Group { offset } => DecodeError::Group { offset: map(offset) },
+            error => error
+        }
+    }
+}
+
+impl From<(data_encoding::DecodeError, &[u8])> for DecodeError {
+    fn from((error, input): (data_encoding::DecodeError, &[u8])) -> Self {
+        let offset = error.position;
+
+        match error.kind {
+            DecodeKind::Symbol => DecodeError::Symbol { offset, byte: input[offset] },
+            DecodeKind::Padding => DecodeError::Padding { offset },
+            DecodeKind::Trailing => DecodeError::Trailing { offset },
+            DecodeKind::Length => DecodeError::Length { offset }
+        }
+    }
+}
+
+/// How strictly encoded input is decoded.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Accept only the canonical form of the encoding, apart from a single line ending after the
+    /// input of any encoding other than raw.
+    #[default]
+    Strict,
+
+    /// Ignore whitespace, accept missing or superfluous padding, and ignore the case of encodings
+    /// whose alphabets are case-insensitive. Percent-encoding and quoted-printable instead accept
+    /// lowercase escapes and pass invalid input through verbatim.
+    Lenient
+}
+
+#[derive(Debug, Error)]
+pub enum EncodeError {
+    #[error("{encoding} encodes multiples of {multiple} bytes, but the input has {length} bytes.")]
+    Length { encoding: &'static str, multiple: usize, length: usize },
+
+    #[error("The human-readable part {0:?} is invalid: {1}")]
+    Hrp(String, bech32::primitives::hrp::Error),
+
+    #[error(transparent)]
+    Bech32(#[from] bech32::EncodeError)
+}
+
+/// A binary-to-text encoding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// The data itself, unencoded.
+    Raw,
+
+    /// Lowercase hexadecimal.
+    Hex,
+
+    /// Uppercase hexadecimal.
+    HexUpper,
+
+    /// Base32 with the RFC 4648 alphabet and padding.
+    Base32,
+
+    /// Base32 with the RFC 4648 alphabet, without padding.
+    Base32NoPad,
+
+    /// Base32 with the RFC 4648 "extended hex" alphabet and padding.
+    Base32Hex,
+
+    /// Base32 with the RFC 4648 "extended hex" alphabet, without padding.
+    Base32HexNoPad,
+
+    /// Crockford's base32, without padding.
+    Crockford,
+
+    /// Base58 with the alphabet used by Bitcoin.
+    Base58,
+
+    /// Base58 with the alphabet used by Flickr.
+    Base58Flickr,
+
+    /// Base64 with the RFC 4648 alphabet and padding.
+    Base64,
+
+    /// Base64 with the RFC 4648 alphabet, without padding.
+    Base64NoPad,
+
+    /// Base64 with the RFC 4648 URL-safe alphabet and padding.
+    Base64Url,
+
+    /// Base64 with the RFC 4648 URL-safe alphabet, without padding.
+    Base64UrlNoPad,
+
+    /// Ascii85, as used by `btoa`.
+    Ascii85,
+
+    /// Z85, as defined by ZeroMQ RFC 32.
+    Z85,
+
+    /// Bech32, as defined by BIP 173, with the given human-readable part.
+    Bech32(String),
+
+    /// Bech32m, as defined by BIP 350, with the given human-readable part.
+    Bech32m(String),
+
+    /// Percent-encoding, as defined by RFC 3986, of every byte other than the unreserved characters.
+    Percent,
+
+    /// Quoted-printable, as defined by RFC 2045.
+    QuotedPrintable
+}
+
+impl Encoding {
+    /// The conventional name of the encoding.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Raw => "Raw",
+            Encoding::Hex | Encoding::HexUpper => "Hexadecimal",
+            Encoding::Base32 | Encoding::Base32NoPad => "Base32",
+            Encoding::Base32Hex | Encoding::Base32HexNoPad => "Base32hex",
+            Encoding::Crockford => "Crockford's base32",
+            Encoding::Base58 | Encoding::Base58Flickr => "Base58",
+            Encoding::Base64 | Encoding::Base64NoPad => "Base64",
+            Encoding::Base64Url | Encoding::Base64UrlNoPad => "Base64url",
+            Encoding::Ascii85 => "Ascii85",
+            Encoding::Z85 => "Z85",
+            Encoding::Bech32(..) => "Bech32",
+            Encoding::Bech32m(..) => "Bech32m",
+            Encoding::Percent => "Percent-encoding",
+            Encoding::QuotedPrintable => "Quoted-printable"
+        }
+    }
+
+    pub fn encode(&self, data: &[u8]) -> Result<Vec<u8>, EncodeError> {
+        let encoded = match self {
+            Encoding::Raw => data.to_vec(),
+            Encoding::Hex => HEXLOWER.encode(data).into_bytes(),
+            Encoding::HexUpper => HEXUPPER.encode(data).into_bytes(),
+            Encoding::Base32 => BASE32.encode(data).into_bytes(),
+            Encoding::Base32NoPad => BASE32_NOPAD.encode(data).into_bytes(),
+            Encoding::Base32Hex => BASE32HEX.encode(data).into_bytes(),
+            Encoding::Base32HexNoPad => BASE32HEX_NOPAD.encode(data).into_bytes(),
+            Encoding::Crockford => CROCKFORD.encode(data).into_bytes(),
+            Encoding::Base58 => bs58::encode(data).with_alphabet(bs58::Alphabet::BITCOIN).into_vec(),
+            Encoding::Base58Flickr => bs58::encode(data).with_alphabet(bs58::Alphabet::FLICKR).into_vec(),
+            Encoding::Base64 => BASE64.encode(data).into_bytes(),
+            Encoding::Base64NoPad => BASE64_NOPAD.encode(data).into_bytes(),
+            Encoding::Base64Url => BASE64URL.encode(data).into_bytes(),
+            Encoding::Base64UrlNoPad => BASE64URL_NOPAD.encode(data).into_bytes(),
+            Encoding::Ascii85 => encode_ascii85(data),
+            Encoding::Z85 => encode_z85(data)
+                .ok_or(EncodeError::Length { encoding: self.name(), multiple: 4, length: data.len() })?,
+            Encoding::Bech32(hrp) => bech32::encode::<Bech32>(parse_hrp(hrp)?, data)?.into_bytes(),
+            Encoding::Bech32m(hrp) => bech32::encode::<Bech32m>(parse_hrp(hrp)?, data)?.into_bytes(),
+            Encoding::Percent => encode_percent(data),
+            Encoding::QuotedPrintable => encode_quoted_printable(data)
+        };
+
+        Ok(encoded)
+    }
+
+    /// Decode `input`, reporting the byte offset in `input` of any invalid character.
+    ///
+    /// The human-readable part of a Bech32 or Bech32m string is checked only if it is not empty.
+    pub fn decode(&self, input: &[u8], mode: Mode) -> Result<Vec<u8>, DecodeError> {
+        match (self, mode) {
+            (Encoding::Raw, _) => Ok(input.to_vec()),
+            (Encoding::QuotedPrintable, mode) => decode_quoted_printable(strip_line_ending(input), mode == Mode::Strict),
+            (encoding, Mode::Strict) => {
+                let input = strip_line_ending(input);
+
+                encoding.decode_canonical(input, true)
+            }
+            (encoding, Mode::Lenient) => {
+                let (cleaned, offsets) = encoding.clean(input);
+
+                encoding.decode_canonical(&cleaned, false)
+                    .map_err(|error| error.map_offset(|offset| offsets.get(offset).copied().unwrap_or(input.len())))
+            }
+        }
+    }
+
+    /// Remove whitespace and, for case-insensitive alphabets, normalize case and padding, returning the
+    /// remaining bytes together with their offsets in `input`.
+    fn clean(&self, input: &[u8]) -> (Vec<u8>, Vec<usize>) {
+        let mut cleaned = Vec::with_capacity(input.len());
+        let mut offsets = Vec::with_capacity(input.len());
+
+        for (offset, &byte) in input.iter().enumerate() {
+            let byte = match (self, byte) {
+                (_, byte) if byte.is_ascii_whitespace() => continue,
+                // Padding is restored or removed as needed once every symbol is known.
+                (Encoding::Base32 | Encoding::Base32NoPad | Encoding::Base32Hex | Encoding::Base32HexNoPad, b'=') => continue,
+                (Encoding::Base64 | Encoding::Base64NoPad | Encoding::Base64Url | Encoding::Base64UrlNoPad, b'=') => continue,
+                (Encoding::Hex, byte) | (Encoding::Bech32(..) | Encoding::Bech32m(..), byte) => byte.to_ascii_lowercase(),
+                (Encoding::HexUpper, byte) => byte.to_ascii_uppercase(),
+                (Encoding::Base32 | Encoding::Base32NoPad | Encoding::Base32Hex | Encoding::Base32HexNoPad, byte) => byte.to_ascii_uppercase(),
+                // Crockford's base32 ignores hyphens and reads I and L as 1 and O as 0.
+                (Encoding::Crockford, b'-') => continue,
+                (Encoding::Crockford, byte) => match byte.to_ascii_uppercase() {
+                    b'I' | b'L' => b'1',
+                    b'O' => b'0',
+                    byte => byte
+                },
+                (_, byte) => byte
+            };
+
+            cleaned.push(byte);
+            offsets.push(offset);
+        }
+
+        // Ascii85 may be delimited by <~ and ~>, as Adobe does.
+        if *self == Encoding::Ascii85 && cleaned.starts_with(b"<~") && cleaned.ends_with(b"~>") && cleaned.len() >= 4 {
+            cleaned.truncate(cleaned.len() - 2);
+            cleaned.drain(..2);
+            offsets.truncate(offsets.len() - 2);
+            offsets.drain(..2);
+        }
+
+        // Restore the padding of padded encodings, which were stripped of whatever padding they had.
+        let block = match self {
+            Encoding::Base32 | Encoding::Base32Hex => 8,
+            Encoding::Base64 | Encoding::Base64Url => 4,
+            _ => 1
+        };
+
+        while !cleaned.len().is_multiple_of(block) {
+            cleaned.push(b'=');
+            offsets.push(input.len());
+        }
+
+        (cleaned, offsets)
+    }
+
+    /// Decode the canonical form of the encoding. Percent-encoding is decoded leniently unless `strict` is set.
+    fn decode_canonical(&self, input: &[u8], strict: bool) -> Result<Vec<u8>, DecodeError> {
+        let decoded = match self {
+            Encoding::Raw => input.to_vec(),
+            Encoding::Hex => decode_data(&HEXLOWER, input)?,
+            Encoding::HexUpper => decode_data(&HEXUPPER, input)?,
+            Encoding::Base32 => decode_data(&BASE32, input)?,
+            Encoding::Base32NoPad => decode_data(&BASE32_NOPAD, input)?,
+            Encoding::Base32Hex => decode_data(&BASE32HEX, input)?,
+            Encoding::Base32HexNoPad => decode_data(&BASE32HEX_NOPAD, input)?,
+            Encoding::Crockford => decode_data(&CROCKFORD, input)?,
+            Encoding::Base58 => decode_base58(input, bs58::Alphabet::BITCOIN)?,
+            Encoding::Base58Flickr => decode_base58(input, bs58::Alphabet::FLICKR)?,
+            Encoding::Base64 => decode_data(&BASE64, input)?,
+            Encoding::Base64NoPad => decode_data(&BASE64_NOPAD, input)?,
+            Encoding::Base64Url => decode_data(&BASE64URL, input)?,
+            Encoding::Base64UrlNoPad => decode_data(&BASE64URL_NOPAD, input)?,
+            Encoding::Ascii85 => decode_ascii85(input)?,
+            Encoding::Z85 => decode_z85(input)?,
+            Encoding::Bech32(expected) => decode_bech32::<Bech32>(input, expected)?,
+            Encoding::Bech32m(expected) => decode_bech32::<Bech32m>(input, expected)?,
+            Encoding::Percent => decode_percent(input, strict)?,
+            Encoding::QuotedPrintable => decode_quoted_printable(input, strict)?
+        };
+
+        Ok(decoded)
+    }
+}
+
+/// Remove a single trailing LF or CRLF line ending.
+fn strip_line_ending(input: &[u8]) -> &[u8] {
+    let input = input.strip_suffix(b"\n").unwrap_or(input);
+
+    input.strip_suffix(b"\r").unwrap_or(input)
+}
+
+/// Decode with `encoding`, preferring to report an invalid character over an incomplete input.
+fn decode_data(encoding: &data_encoding::Encoding, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    encoding.decode(input).map_err(|error| match error.kind {
+        DecodeKind::Length => match encoding.decode(&input[..error.position]) {
+            Err(prefix) if prefix.kind == DecodeKind::Symbol => DecodeError::from((prefix, input)),
+            _ => DecodeError::from((error, input))
+        },
+        _ => DecodeError::from((error, input))
+    })
+}
+
+fn decode_base58(input: &[u8], alphabet: &bs58::Alphabet) -> Result<Vec<u8>, DecodeError> {
+    bs58::decode(input).with_alphabet(alphabet).into_vec().map_err(|error| match error {
+        bs58::decode::Error::InvalidCharacter { index, .. } | bs58::decode::Error::NonAsciiCharacter { index } => {
+            DecodeError::Symbol { offset: index, byte: input[index] }
+        }
+        _ => DecodeError::Length { offset: input.len() }
+    })
+}
+
+/// Decode a string with the checksum `Ck`, and the human-readable part `expected` unless it is empty.
+fn decode_bech32<Ck: Checksum>(input: &[u8], expected: &str) -> Result<Vec<u8>, DecodeError> {
+    let string = std::str::from_utf8(input).map_err(|error| {
+        let offset = error.valid_up_to();
+
+        DecodeError::Symbol { offset, byte: input[offset] }
+    })?;
+
+    let checked = CheckedHrpstring::new::<Ck>(string)?;
+
+    if !expected.is_empty() && !checked.hrp().as_str().eq_ignore_ascii_case(expected) {
+        return Err(DecodeError::Hrp { expected: expected.to_string(), found: checked.hrp().to_string() })
+    }
+
+    Ok(checked.byte_iter().collect())
+}
+
+fn parse_hrp(hrp: &str) -> Result<Hrp, EncodeError> {
+    Hrp::parse(hrp).map_err(|error| EncodeError::Hrp(hrp.to_string(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecodeError, EncodeError, Encoding, Mode};
+
+    fn encode(encoding: Encoding, data: &[u8]) -> String {
+        String::from_utf8(encoding.encode(data).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn can_encode_base32() {
+        assert_eq!(encode(Encoding::Base32, b"foobar"), "MZXW6YTBOI======");
+        assert_eq!(encode(Encoding::Base32NoPad, b"foobar"), "MZXW6YTBOI");
+        assert_eq!(encode(Encoding::Base32Hex, b"foobar"), "CPNMUOJ1E8======");
+        assert_eq!(encode(Encoding::Crockford, b"foobar"), "CSQPYRK1E8");
+    }
+
+    #[test]
+    fn can_encode_base58() {
+        assert_eq!(encode(Encoding::Base58, b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(encode(Encoding::Base58Flickr, b"hello world"), "rTu1dk6cWsRYjYu");
+        assert_eq!(encode(Encoding::Base58, b"\0\0a"), "112g");
+    }
+
+    #[test]
+    fn can_encode_base64_without_padding() {
+        assert_eq!(encode(Encoding::Base64, b"fo"), "Zm8=");
+        assert_eq!(encode(Encoding::Base64NoPad, b"fo"), "Zm8");
+        assert_eq!(encode(Encoding::Base64UrlNoPad, b"\xfb\xff"), "-_8");
+    }
+
+    #[test]
+    fn can_encode_bech32() {
+        // Test vectors given in BIP 173 and BIP 350.
+        assert_eq!(encode(Encoding::Bech32(String::from("a")), b""), "a12uel5l");
+        assert_eq!(encode(Encoding::Bech32m(String::from("a")), b""), "a1lqfn3a");
+        assert!(matches!(Encoding::Bech32(String::new()).encode(b""), Err(EncodeError::Hrp(..))));
+    }
+
+    #[test]
+    fn rejects_partial_z85_groups() {
+        assert!(matches!(Encoding::Z85.encode(b"abcde"), Err(EncodeError::Length { multiple: 4, length: 5, .. })));
+    }
+
+    #[test]
+    fn can_decode_strictly() {
+        assert_eq!(Encoding::Hex.decode(b"666f6f\n", Mode::Strict).unwrap(), b"foo");
+        assert_eq!(Encoding::Base32.decode(b"MZXW6YTBOI======", Mode::Strict).unwrap(), b"foobar");
+        assert_eq!(Encoding::Base58.decode(b"StV1DL6CwTryKyV\r\n", Mode::Strict).unwrap(), b"hello world");
+        assert_eq!(Encoding::Base64Url.decode(b"-_8=", Mode::Strict).unwrap(), b"\xfb\xff");
+        assert_eq!(Encoding::Bech32(String::from("a")).decode(b"a12uel5l", Mode::Strict).unwrap(), b"");
+    }
+
+    #[test]
+    fn reports_offsets_of_invalid_characters() {
+        assert!(matches!(Encoding::Hex.decode(b"66G6", Mode::Strict), Err(DecodeError::Symbol { offset: 2, byte: b'G' })));
+        assert!(matches!(Encoding::Hex.decode(b"66 6f", Mode::Strict), Err(DecodeError::Symbol { offset: 2, byte: b' ' })));
+        assert!(matches!(Encoding::Base58.decode(b"St0V", Mode::Strict), Err(DecodeError::Symbol { offset: 2, byte: b'0' })));
+        assert!(matches!(Encoding::Base64.decode(b"Zm8", Mode::Strict), Err(DecodeError::Length { .. })));
+    }
+
+    #[test]
+    fn can_decode_leniently() {
+        assert_eq!(Encoding::Hex.decode(b"66 6F\n6f\n", Mode::Lenient).unwrap(), b"foo");
+        assert_eq!(Encoding::Base32.decode(b"mzxw6ytboi", Mode::Lenient).unwrap(), b"foobar");
+        assert_eq!(Encoding::Base64.decode(b"Zm9v\nYmFy\nZm8", Mode::Lenient).unwrap(), b"foobarfo");
+        assert_eq!(Encoding::Base64NoPad.decode(b"Zm8=", Mode::Lenient).unwrap(), b"fo");
+        assert_eq!(Encoding::Crockford.decode(b"csqp-yrkl-e8", Mode::Lenient).unwrap(), b"foobar");
+        assert_eq!(Encoding::Ascii85.decode(b"<~87cURD_*#4DfTZ)+T~>", Mode::Lenient).unwrap(), b"Hello, World!");
+    }
+
+    #[test]
+    fn reports_original_offsets_when_lenient() {
+        assert!(matches!(Encoding::Hex.decode(b"66 6\n x", Mode::Lenient), Err(DecodeError::Symbol { offset: 6, byte: b'x' })));
+    }
+
+    #[test]
+    fn checks_the_bech32_hrp() {
+        assert!(matches!(Encoding::Bech32(String::from("b")).decode(b"a12uel5l", Mode::Strict), Err(DecodeError::Hrp { .. })));
+        assert_eq!(Encoding::Bech32(String::new()).decode(b"A12UEL5L", Mode::Lenient).unwrap(), b"");
+    }
+
+    #[test]
+    fn checks_the_bech32_variant() {
+        for mode in [Mode::Strict, Mode::Lenient] {
+            assert!(matches!(Encoding::Bech32(String::from("a")).decode(b"a1lqfn3a", mode), Err(DecodeError::Bech32(..))));
+            assert!(matches!(Encoding::Bech32m(String::from("a")).decode(b"a12uel5l", mode), Err(DecodeError::Bech32(..))));
+        }
+    }
+
+    #[test]
+    fn round_trips_every_encoding() {
+        let data = b"\0\x01 binary = data\xff\r\n";
+        let encodings = [
+            Encoding::Raw, Encoding::Hex, Encoding::HexUpper, Encoding::Base32, Encoding::Base32NoPad, Encoding::Base32Hex,
+            Encoding::Base32HexNoPad, Encoding::Crockford, Encoding::Base58, Encoding::Base58Flickr, Encoding::Base64,
+            Encoding::Base64NoPad, Encoding::Base64Url, Encoding::Base64UrlNoPad, Encoding::Ascii85,
+            Encoding::Bech32(String::from("grad")), Encoding::Bech32m(String::from("grad")), Encoding::Percent,
+            Encoding::QuotedPrintable
+        ];
+
+        for encoding in encodings {
+            let encoded = encoding.encode(data).unwrap();
+
+            assert_eq!(encoding.decode(&encoded, Mode::Strict).unwrap(), data, "{}", encoding.name());
+            assert_eq!(encoding.decode(&encoded, Mode::Lenient).unwrap(), data, "{}", encoding.name());
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Percent-encoding, as defined by RFC 3986.
+
+use crate::DecodeError;
+use crate::hex::{HEX, hex_value};
+
+/// Whether a byte is unreserved by RFC 3986, and so is never percent-encoded.
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+/// Percent-encode every byte of `data` other than the unreserved characters.
+pub fn encode_percent(data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(data.len() * 3);
+
+    for &byte in data {
+        if is_unreserved(byte) { output.push(byte) }
+        else { output.extend_from_slice(&[b'%', HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]]) }
+    }
+
+    output
+}
+
+/// Decode percent-encoded data.
+///
+/// Strict decoding accepts only unreserved characters and percent-encoded bytes with uppercase
+/// digits. Lenient decoding accepts lowercase digits and passes any other byte through verbatim,
+/// including a `%` which does not begin a valid escape.
+pub fn decode_percent(input: &[u8], strict: bool) -> Result<Vec<u8>, DecodeError> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut offset = 0;
+
+    while offset < input.len() {
+        let byte = input[offset];
+
+        if byte != b'%' {
+            if strict && !is_unreserved(byte) { return Err(DecodeError::Symbol { offset, byte }) }
+
+            output.push(byte);
+            offset += 1;
+
+            continue;
+        }
+
+        let high = input.get(offset + 1).map(|&digit| (digit, hex_value(digit, !strict)));
+        let low = input.get(offset + 2).map(|&digit| (digit, hex_value(digit, !strict)));
+
+        match (high, low) {
+            (Some((_, Some(high))), Some((_, Some(low)))) => {
+                output.push(high << 4 | low);
+                offset += 3;
+            }
+            _ if !strict => {
+                output.push(byte);
+                offset += 1;
+            }
+            (None, _) | (Some((_, Some(_))), None) => return Err(DecodeError::Length { offset: input.len() }),
+            (Some((digit, None)), _) => return Err(DecodeError::Symbol { offset: offset + 1, byte: digit }),
+            (_, Some((digit, None))) => return Err(DecodeError::Symbol { offset: offset + 2, byte: digit })
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DecodeError;
+
+    #[test]
+    fn can_encode_percent() {
+        assert_eq!(super::encode_percent(b"a b/c~\xff"), b"a%20b%2Fc~%FF");
+    }
+
+    #[test]
+    fn can_decode_percent_strictly() {
+        assert_eq!(super::decode_percent(b"a%20b%2Fc~%FF", true).unwrap(), b"a b/c~\xff");
+        assert!(matches!(super::decode_percent(b"a%2fc", true), Err(DecodeError::Symbol { offset: 3, byte: b'f' })));
+        assert!(matches!(super::decode_percent(b"a/c", true), Err(DecodeError::Symbol { offset: 1, byte: b'/' })));
+        assert!(matches!(super::decode_percent(b"a%2", true), Err(DecodeError::Length { offset: 3 })));
+    }
+
+    #[test]
+    fn can_decode_percent_leniently() {
+        assert_eq!(super::decode_percent(b"a%2fc/100%", false).unwrap(), b"a/c/100%");
+    }
+}
@@ -0,0 +1,137 @@
+//! The quoted-printable encoding, as defined by RFC 2045.
+//!
+//! Input is treated as binary, so line breaks in the input are themselves encoded and every line
+//! break in the output is a soft line break.
+
+use crate::DecodeError;
+use crate::hex::{HEX, hex_value};
+
+/// The maximum length of an encoded line, excluding its line break.
+const LINE_LENGTH: usize = 76;
+
+/// Encode `data` as quoted-printable.
+pub fn encode_quoted_printable(data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(data.len() * 3);
+    let mut line = 0;
+
+    for (index, &byte) in data.iter().enumerate() {
+        let literal = matches!(byte, b'!'..=b'<' | b'>'..=b'~')
+            || (matches!(byte, b' ' | b'\t') && index + 1 < data.len());
+
+        let token: &[u8] = if literal { &[byte] } else { &[b'=', HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]] };
+
+        // Leave room for the = which marks a soft line break.
+        if line + token.len() > LINE_LENGTH - 1 {
+            output.extend_from_slice(b"=\r\n");
+            line = 0;
+        }
+
+        output.extend_from_slice(token);
+        line += token.len();
+    }
+
+    output
+}
+
+/// Decode quoted-printable data.
+///
+/// Strict decoding accepts only printable characters, spaces, tabs, CRLF line breaks and escapes with
+/// uppercase digits. Lenient decoding also accepts lowercase digits and LF line breaks, removes
+/// whitespace before line breaks, and passes any other byte through verbatim, including an `=` which
+/// does not begin a valid escape.
+pub fn decode_quoted_printable(input: &[u8], strict: bool) -> Result<Vec<u8>, DecodeError> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut offset = 0;
+
+    while offset < input.len() {
+        let byte = input[offset];
+        let rest = &input[offset + 1..];
+
+        match byte {
+            b'=' => {
+                let whitespace = if strict { 0 } else { rest.iter().take_while(|&&byte| matches!(byte, b' ' | b'\t')).count() };
+                let rest = &rest[whitespace..];
+
+                if rest.starts_with(b"\r\n") {
+                    offset += 1 + whitespace + 2;
+                }
+                else if !strict && rest.starts_with(b"\n") {
+                    offset += 1 + whitespace + 1;
+                }
+                else {
+                    match (rest.first().map(|&digit| hex_value(digit, !strict)), rest.get(1).map(|&digit| hex_value(digit, !strict))) {
+                        (Some(Some(high)), Some(Some(low))) => {
+                            output.push(high << 4 | low);
+                            offset += 3;
+                        }
+                        _ if !strict => {
+                            output.push(byte);
+                            offset += 1;
+                        }
+                        (None, _) | (Some(Some(_)), None) => return Err(DecodeError::Length { offset: input.len() }),
+                        (Some(None), _) => return Err(DecodeError::Symbol { offset: offset + 1, byte: rest[0] }),
+                        (_, Some(None)) => return Err(DecodeError::Symbol { offset: offset + 2, byte: rest[1] })
+                    }
+                }
+            }
+            b' ' | b'\t' if !strict => {
+                // Whitespace at the end of a line was added in transport and is not part of the data.
+                let whitespace = 1 + rest.iter().take_while(|&&byte| matches!(byte, b' ' | b'\t')).count();
+                let following = &input[offset + whitespace..];
+
+                if !(following.is_empty() || following.starts_with(b"\n") || following.starts_with(b"\r\n")) {
+                    output.extend_from_slice(&input[offset..offset + whitespace]);
+                }
+
+                offset += whitespace;
+            }
+            b'\r' if rest.starts_with(b"\n") => {
+                output.extend_from_slice(b"\r\n");
+                offset += 2;
+            }
+            b'!'..=b'~' | b' ' | b'\t' => {
+                output.push(byte);
+                offset += 1;
+            }
+            _ if !strict => {
+                output.push(byte);
+                offset += 1;
+            }
+            _ => return Err(DecodeError::Symbol { offset, byte })
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DecodeError;
+
+    #[test]
+    fn can_encode_quoted_printable() {
+        assert_eq!(super::encode_quoted_printable(b"caf\xc3\xa9 = 1\n "), b"caf=C3=A9 =3D 1=0A=20");
+    }
+
+    #[test]
+    fn wraps_long_lines() {
+        let encoded = super::encode_quoted_printable(&[b'a'; 100]);
+        let lines: Vec<&[u8]> = encoded.split(|&byte| byte == b'\n').collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), 76 + 1);
+        assert_eq!(super::decode_quoted_printable(&encoded, true).unwrap(), [b'a'; 100]);
+    }
+
+    #[test]
+    fn can_decode_quoted_printable_strictly() {
+        assert_eq!(super::decode_quoted_printable(b"caf=C3=A9 =3D 1=0A=20", true).unwrap(), b"caf\xc3\xa9 = 1\n ");
+        assert!(matches!(super::decode_quoted_printable(b"caf=c3", true), Err(DecodeError::Symbol { offset: 4, byte: b'c' })));
+        assert!(matches!(super::decode_quoted_printable(b"a\nb", true), Err(DecodeError::Symbol { offset: 1, byte: b'\n' })));
+    }
+
+    #[test]
+    fn can_decode_quoted_printable_leniently() {
+        assert_eq!(super::decode_quoted_printable(b"caf=c3=a9  \nsoft=\nbreak = 1", false).unwrap(), b"caf\xc3\xa9\nsoftbreak = 1");
+    }
+}
@@ -0,0 +1,127 @@
+//! Checksum files in the format of GNU coreutils, as written by `sha256sum` and read by `sha256sum --check`.
+//!
+//! Each line holds a digest, a space, a space or `*` marking text or binary mode, and a file name. A
+//! line which begins with `\` has a file name in which backslashes, line feeds and carriage returns
+//! are escaped.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ParseChecksumError {
+    #[error("Line {0} is not a checksum line.")]
+    Format(usize),
+
+    #[error("Line {0} has an invalid escape in its file name.")]
+    Escape(usize)
+}
+
+/// A line of a checksum file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The encoded digest.
+    pub digest: String,
+
+    /// The name of the file.
+    pub name: String
+}
+
+impl Entry {
+    pub fn new(digest: impl Into<String>, name: impl Into<String>) -> Self {
+        Entry { digest: digest.into(), name: name.into() }
+    }
+
+    /// Parse the line with the given one-based number.
+    pub fn parse(line: &str, number: usize) -> Result<Self, ParseChecksumError> {
+        let (escaped, line) = match line.strip_prefix('\\') {
+            Some(line) => (true, line),
+            None => (false, line)
+        };
+
+        let (digest, rest) = line.split_once(' ').ok_or(ParseChecksumError::Format(number))?;
+
+        let name = rest.strip_prefix(' ')
+            .or_else(|| rest.strip_prefix('*'))
+            .filter(|name| !digest.is_empty() && !name.is_empty())
+            .ok_or(ParseChecksumError::Format(number))?;
+
+        let name = if escaped { unescape(name).ok_or(ParseChecksumError::Escape(number))? } else { name.to_string() };
+
+        Ok(Entry::new(digest, name))
+    }
+}
+
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.name.contains(['\\', '\n', '\r']) {
+            let name = self.name.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r");
+
+            write!(f, "\\{}  {}", self.digest, name)
+        }
+        else {
+            write!(f, "{}  {}", self.digest, self.name)
+        }
+    }
+}
+
+fn unescape(name: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut chars = name.chars();
+
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            unescaped.push(char);
+
+            continue;
+        }
+
+        match chars.next()? {
+            '\\' => unescaped.push('\\'),
+            'n' => unescaped.push('\n'),
+            'r' => unescaped.push('\r'),
+            _ => return None
+        }
+    }
+
+    Some(unescaped)
+}
+
+/// Parse every line of a checksum file, ignoring blank lines and lines beginning with `#`.
+pub fn parse(contents: &str) -> impl Iterator<Item = Result<Entry, ParseChecksumError>> {
+    contents.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.strip_suffix('\r').unwrap_or(line)))
+        .filter(|(_, line)| !(line.trim().is_empty() || line.starts_with('#')))
+        .map(|(number, line)| Entry::parse(line, number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Entry, ParseChecksumError};
+
+    #[test]
+    fn can_parse_checksum_lines() {
+        assert_eq!(Entry::parse("abc123  file name.txt", 1).unwrap(), Entry::new("abc123", "file name.txt"));
+        assert_eq!(Entry::parse("abc123 *image.png", 1).unwrap(), Entry::new("abc123", "image.png"));
+        assert_eq!(Entry::parse("\\abc123  a\\\\b\\nc", 1).unwrap(), Entry::new("abc123", "a\\b\nc"));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(matches!(Entry::parse("abc123", 3), Err(ParseChecksumError::Format(3))));
+        assert!(matches!(Entry::parse("abc123 file", 3), Err(ParseChecksumError::Format(3))));
+        assert!(matches!(Entry::parse("\\abc123  a\\b", 4), Err(ParseChecksumError::Escape(4))));
+    }
+
+    #[test]
+    fn escapes_file_names_when_formatting() {
+        assert_eq!(Entry::new("abc123", "file.txt").to_string(), "abc123  file.txt");
+        assert_eq!(Entry::new("abc123", "a\\b\nc").to_string(), "\\abc123  a\\\\b\\nc");
+    }
+
+    #[test]
+    fn skips_comments_and_blank_lines() {
+        let entries: Vec<Entry> = super::parse("# comment\n\nabc  a\r\ndef *b\n").map(Result::unwrap).collect();
+
+        assert_eq!(entries, [Entry::new("abc", "a"), Entry::new("def", "b")]);
+    }
+}
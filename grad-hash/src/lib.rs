@@ -0,0 +1,313 @@
+//! Cryptographic hash functions, checksums and fast non-cryptographic hashes.
+
+use std::hash::Hasher as _;
+use std::io::{ErrorKind, Read};
+
+use digest::DynDigest;
+use siphasher::sip128::Hasher128;
+
+pub mod check;
+pub mod mac;
+
+/// The size of the buffer with which readers are hashed.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// A hash function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Algorithm {
+    /// SHA-224.
+    Sha224,
+
+    /// SHA-256.
+    Sha256,
+
+    /// SHA-384.
+    Sha384,
+
+    /// SHA-512.
+    Sha512,
+
+    /// SHA-512 truncated to 224 bits.
+    #[cfg_attr(feature = "clap", value(name = "sha512-224"))]
+    Sha512_224,
+
+    /// SHA-512 truncated to 256 bits.
+    #[cfg_attr(feature = "clap", value(name = "sha512-256"))]
+    Sha512_256,
+
+    /// SHA3-224.
+    #[cfg_attr(feature = "clap", value(name = "sha3-224"))]
+    Sha3_224,
+
+    /// SHA3-256.
+    #[cfg_attr(feature = "clap", value(name = "sha3-256"))]
+    Sha3_256,
+
+    /// SHA3-384.
+    #[cfg_attr(feature = "clap", value(name = "sha3-384"))]
+    Sha3_384,
+
+    /// SHA3-512.
+    #[cfg_attr(feature = "clap", value(name = "sha3-512"))]
+    Sha3_512,
+
+    /// BLAKE2b with a 512-bit digest, as printed by b2sum.
+    Blake2b,
+
+    /// BLAKE2s with a 256-bit digest.
+    Blake2s,
+
+    /// BLAKE3 with a 256-bit digest, as printed by b3sum.
+    Blake3,
+
+    /// MD5. Legacy: broken, and suitable only for verifying existing checksums.
+    Md5,
+
+    /// SHA-1. Legacy: broken, and suitable only for verifying existing checksums.
+    Sha1,
+
+    /// CRC-32, as used by zlib, gzip and PNG. Not cryptographic.
+    Crc32,
+
+    /// CRC-32C (Castagnoli), as used by iSCSI, ext4 and SCTP. Not cryptographic.
+    Crc32c,
+
+    /// XXH32 with a seed of zero. Not cryptographic.
+    Xxh32,
+
+    /// XXH64 with a seed of zero. Not cryptographic.
+    Xxh64,
+
+    /// XXH3 with a 64-bit digest. Not cryptographic.
+    #[cfg_attr(feature = "clap", value(name = "xxh3"))]
+    Xxh3_64,
+
+    /// XXH3 with a 128-bit digest. Not cryptographic.
+    #[cfg_attr(feature = "clap", value(name = "xxh128"))]
+    Xxh3_128,
+
+    /// SipHash-2-4 with a 64-bit digest and a key of zeros. Not cryptographic without a secret key.
+    #[cfg_attr(feature = "clap", value(name = "siphash"))]
+    SipHash,
+
+    /// SipHash-2-4 with a 128-bit digest and a key of zeros. Not cryptographic without a secret key.
+    #[cfg_attr(feature = "clap", value(name = "siphash128"))]
+    SipHash128
+}
+
+impl Algorithm {
+    /// The conventional name of the algorithm.
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Sha224 => "SHA-224",
+            Algorithm::Sha256 => "SHA-256",
+            Algorithm::Sha384 => "SHA-384",
+            Algorithm::Sha512 => "SHA-512",
+            Algorithm::Sha512_224 => "SHA-512/224",
+            Algorithm::Sha512_256 => "SHA-512/256",
+            Algorithm::Sha3_224 => "SHA3-224",
+            Algorithm::Sha3_256 => "SHA3-256",
+            Algorithm::Sha3_384 => "SHA3-384",
+            Algorithm::Sha3_512 => "SHA3-512",
+            Algorithm::Blake2b => "BLAKE2b-512",
+            Algorithm::Blake2s => "BLAKE2s-256",
+            Algorithm::Blake3 => "BLAKE3",
+            Algorithm::Md5 => "MD5",
+            Algorithm::Sha1 => "SHA-1",
+            Algorithm::Crc32 => "CRC-32",
+            Algorithm::Crc32c => "CRC-32C",
+            Algorithm::Xxh32 => "XXH32",
+            Algorithm::Xxh64 => "XXH64",
+            Algorithm::Xxh3_64 => "XXH3-64",
+            Algorithm::Xxh3_128 => "XXH3-128",
+            Algorithm::SipHash => "SipHash-2-4",
+            Algorithm::SipHash128 => "SipHash-2-4-128"
+        }
+    }
+
+    /// Whether the algorithm is a broken cryptographic hash function which is supported only for
+    /// compatibility with existing checksums.
+    pub fn is_legacy(self) -> bool {
+        matches!(self, Algorithm::Md5 | Algorithm::Sha1)
+    }
+
+    /// Begin hashing with the algorithm.
+    pub fn hasher(self) -> Hasher {
+        let state = match self {
+            Algorithm::Sha224 => State::Digest(Box::new(sha2::Sha224::default())),
+            Algorithm::Sha256 => State::Digest(Box::new(sha2::Sha256::default())),
+            Algorithm::Sha384 => State::Digest(Box::new(sha2::Sha384::default())),
+            Algorithm::Sha512 => State::Digest(Box::new(sha2::Sha512::default())),
+            Algorithm::Sha512_224 => State::Digest(Box::new(sha2::Sha512_224::default())),
+            Algorithm::Sha512_256 => State::Digest(Box::new(sha2::Sha512_256::default())),
+            Algorithm::Sha3_224 => State::Digest(Box::new(sha3::Sha3_224::default())),
+            Algorithm::Sha3_256 => State::Digest(Box::new(sha3::Sha3_256::default())),
+            Algorithm::Sha3_384 => State::Digest(Box::new(sha3::Sha3_384::default())),
+            Algorithm::Sha3_512 => State::Digest(Box::new(sha3::Sha3_512::default())),
+            Algorithm::Blake2b => State::Digest(Box::new(blake2::Blake2b512::default())),
+            Algorithm::Blake2s => State::Digest(Box::new(blake2::Blake2s256::default())),
+            Algorithm::Md5 => State::Digest(Box::new(md5::Md5::default())),
+            Algorithm::Sha1 => State::Digest(Box::new(sha1::Sha1::default())),
+            Algorithm::Blake3 => State::Blake3(Box::new(blake3::Hasher::new())),
+            Algorithm::Crc32 => State::Crc32(crc32fast::Hasher::new()),
+            Algorithm::Crc32c => State::Crc32c(0),
+            Algorithm::Xxh32 => State::Xxh32(xxhash_rust::xxh32::Xxh32::new(0)),
+            Algorithm::Xxh64 => State::Xxh64(xxhash_rust::xxh64::Xxh64::new(0)),
+            Algorithm::Xxh3_64 | Algorithm::Xxh3_128 => State::Xxh3(Box::new(xxhash_rust::xxh3::Xxh3::new())),
+            Algorithm::SipHash => State::SipHash(siphasher::sip::SipHasher24::new()),
+            Algorithm::SipHash128 => State::SipHash128(siphasher::sip128::SipHasher24::new())
+        };
+
+        Hasher { algorithm: self, state }
+    }
+
+    /// Hash `data` in one call.
+    pub fn hash(self, data: &[u8]) -> Vec<u8> {
+        let mut hasher = self.hasher();
+
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Hash everything read from `reader`.
+    pub fn hash_reader(self, reader: impl Read) -> std::io::Result<Vec<u8>> {
+        let mut hasher = self.hasher();
+
+        read_chunks(reader, |chunk| hasher.update(chunk))?;
+
+        Ok(hasher.finalize())
+    }
+}
+
+/// Pass everything read from `reader` to `consume`, a buffer at a time.
+fn read_chunks(mut reader: impl Read, mut consume: impl FnMut(&[u8])) -> std::io::Result<()> {
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(length) => consume(&buffer[..length]),
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error)
+        }
+    }
+}
+
+enum State {
+    Digest(Box<dyn DynDigest + Send>),
+    Blake3(Box<blake3::Hasher>),
+    Crc32(crc32fast::Hasher),
+    Crc32c(u32),
+    Xxh32(xxhash_rust::xxh32::Xxh32),
+    Xxh64(xxhash_rust::xxh64::Xxh64),
+    Xxh3(Box<xxhash_rust::xxh3::Xxh3>),
+    SipHash(siphasher::sip::SipHasher24),
+    SipHash128(siphasher::sip128::SipHasher24)
+}
+
+/// The incremental state of a hash function.
+///
+/// Checksums and non-cryptographic hashes are finalized as big-endian integers, as printed by
+/// `cksum`-style tools and `xxhsum`, except for SipHash, whose digest is defined as little-endian.
+pub struct Hasher {
+    algorithm: Algorithm,
+    state: State
+}
+
+impl Hasher {
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            State::Digest(digest) => digest.update(data),
+            State::Blake3(hasher) => { hasher.update(data); }
+            State::Crc32(hasher) => hasher.update(data),
+            State::Crc32c(crc) => *crc = crc32c::crc32c_append(*crc, data),
+            State::Xxh32(hasher) => hasher.update(data),
+            State::Xxh64(hasher) => hasher.update(data),
+            State::Xxh3(hasher) => hasher.update(data),
+            State::SipHash(hasher) => hasher.write(data),
+            State::SipHash128(hasher) => hasher.write(data)
+        }
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        match self.state {
+            State::Digest(digest) => digest.finalize().into_vec(),
+            State::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+            State::Crc32(hasher) => hasher.finalize().to_be_bytes().to_vec(),
+            State::Crc32c(crc) => crc.to_be_bytes().to_vec(),
+            State::Xxh32(hasher) => hasher.digest().to_be_bytes().to_vec(),
+            State::Xxh64(hasher) => hasher.digest().to_be_bytes().to_vec(),
+            State::Xxh3(hasher) if self.algorithm == Algorithm::Xxh3_128 => hasher.digest128().to_be_bytes().to_vec(),
+            State::Xxh3(hasher) => hasher.digest().to_be_bytes().to_vec(),
+            State::SipHash(hasher) => hasher.finish().to_le_bytes().to_vec(),
+            State::SipHash128(hasher) => hasher.finish128().as_bytes().to_vec()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Algorithm;
+
+    fn hex(algorithm: Algorithm, data: &[u8]) -> String {
+        algorithm.hash(data).iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn can_hash_with_sha2_and_sha3() {
+        assert_eq!(hex(Algorithm::Sha256, b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(hex(Algorithm::Sha3_256, b"abc"), "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532");
+        assert_eq!(Algorithm::Sha512.hash(b"").len(), 64);
+    }
+
+    #[test]
+    fn can_hash_with_blake() {
+        assert_eq!(hex(Algorithm::Blake3, b""), "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262");
+        assert_eq!(hex(Algorithm::Blake2s, b"abc"), "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982");
+    }
+
+    #[test]
+    fn can_hash_with_legacy_algorithms() {
+        assert_eq!(hex(Algorithm::Md5, b""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(hex(Algorithm::Sha1, b"abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert!(Algorithm::Md5.is_legacy() && !Algorithm::Sha256.is_legacy());
+    }
+
+    #[test]
+    fn can_compute_checksums() {
+        // The check values of the CRC catalogue.
+        assert_eq!(hex(Algorithm::Crc32, b"123456789"), "cbf43926");
+        assert_eq!(hex(Algorithm::Crc32c, b"123456789"), "e3069283");
+    }
+
+    #[test]
+    fn can_hash_with_xxhash() {
+        assert_eq!(hex(Algorithm::Xxh32, b""), "02cc5d05");
+        assert_eq!(hex(Algorithm::Xxh64, b""), "ef46db3751d8e999");
+        assert_eq!(hex(Algorithm::Xxh3_64, b""), "2d06800538d394c2");
+        assert_eq!(Algorithm::Xxh3_128.hash(b"").len(), 16);
+    }
+
+    #[test]
+    fn can_hash_with_siphash() {
+        // The reference implementation of SipHash-2-4 with a key of zeros, whose digests are little-endian.
+        assert_eq!(hex(Algorithm::SipHash, b""), "d70077739d4b921e");
+        assert_eq!(hex(Algorithm::SipHash, b"abc"), "deee70479684c83f");
+        assert_eq!(hex(Algorithm::SipHash128, b""), "5049d74780a3e07d4202ab47d4cef2f4");
+        assert_eq!(hex(Algorithm::SipHash128, b"abc"), "6c95dec302962fa8ca5e69c1d5d15478");
+    }
+
+    #[test]
+    fn hashes_readers_incrementally() {
+        let data = vec![0x5a; 200_000];
+
+        for algorithm in [Algorithm::Sha256, Algorithm::Blake3, Algorithm::Crc32c, Algorithm::Xxh3_128, Algorithm::SipHash] {
+            assert_eq!(algorithm.hash_reader(data.as_slice()).unwrap(), algorithm.hash(&data), "{}", algorithm.name());
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Message authentication codes.
+
+use std::io::Read;
+
+use hmac::{Hmac, Mac as _};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+use tiny_keccak::{Hasher as _, Kmac};
+
+#[derive(Debug, Error)]
+pub enum MacError {
+    #[error("{algorithm} requires a key of exactly {expected} bytes, but the key has {length} bytes.")]
+    KeyLength { algorithm: &'static str, expected: usize, length: usize },
+
+    #[error("The key must not be empty.")]
+    EmptyKey
+}
+
+/// A message authentication code.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum MacAlgorithm {
+    /// HMAC with SHA-256, as defined by RFC 2104.
+    #[cfg_attr(feature = "clap", value(name = "hmac-sha256"))]
+    HmacSha256,
+
+    /// HMAC with SHA-512, as defined by RFC 2104.
+    #[cfg_attr(feature = "clap", value(name = "hmac-sha512"))]
+    HmacSha512,
+
+    /// BLAKE3 in keyed mode, with a 256-bit digest. Requires a key of exactly 32 bytes.
+    #[cfg_attr(feature = "clap", value(name = "blake3-keyed"))]
+    Blake3Keyed,
+
+    /// KMAC128, as defined by NIST SP 800-185, with an empty customization string and a 256-bit digest.
+    Kmac128,
+
+    /// KMAC256, as defined by NIST SP 800-185, with an empty customization string and a 512-bit digest.
+    #[cfg_attr(feature = "clap", value(alias = "kmac"))]
+    Kmac256
+}
+
+impl MacAlgorithm {
+    /// The conventional name of the algorithm.
+    pub fn name(self) -> &'static str {
+        match self {
+            MacAlgorithm::HmacSha256 => "HMAC-SHA-256",
+            MacAlgorithm::HmacSha512 => "HMAC-SHA-512",
+            MacAlgorithm::Blake3Keyed => "BLAKE3 (keyed)",
+            MacAlgorithm::Kmac128 => "KMAC128",
+            MacAlgorithm::Kmac256 => "KMAC256"
+        }
+    }
+
+    /// The length in bytes of a key which provides the full security of the algorithm.
+    pub fn key_length(self) -> usize {
+        match self {
+            MacAlgorithm::HmacSha256 | MacAlgorithm::Blake3Keyed | MacAlgorithm::Kmac256 => 32,
+            MacAlgorithm::HmacSha512 => 64,
+            MacAlgorithm::Kmac128 => 16
+        }
+    }
+
+    /// The length in bytes of a code.
+    pub fn output_length(self) -> usize {
+        match self {
+            MacAlgorithm::HmacSha256 | MacAlgorithm::Blake3Keyed | MacAlgorithm::Kmac128 => 32,
+            MacAlgorithm::HmacSha512 | MacAlgorithm::Kmac256 => 64
+        }
+    }
+
+    /// Begin authenticating a message with `key`.
+    ///
+    /// Keyed BLAKE3 requires a key of exactly 32 bytes. The other algorithms accept keys of any non-zero length.
+    pub fn mac(self, key: &[u8]) -> Result<Mac, MacError> {
+        if key.is_empty() { return Err(MacError::EmptyKey) }
+
+        let state = match self {
+            MacAlgorithm::HmacSha256 => State::HmacSha256(Box::new(
+                Hmac::new_from_slice(key).map_err(|_| MacError::EmptyKey)?
+            )),
+            MacAlgorithm::HmacSha512 => State::HmacSha512(Box::new(
+                Hmac::new_from_slice(key).map_err(|_| MacError::EmptyKey)?
+            )),
+            MacAlgorithm::Blake3Keyed => {
+                let key: &[u8; 32] = key.try_into()
+                    .map_err(|_| MacError::KeyLength { algorithm: self.name(), expected: 32, length: key.len() })?;
+
+                State::Blake3(Box::new(blake3::Hasher::new_keyed(key)))
+            }
+            MacAlgorithm::Kmac128 => State::Kmac(Box::new(Kmac::v128(key, b""))),
+            MacAlgorithm::Kmac256 => State::Kmac(Box::new(Kmac::v256(key, b"")))
+        };
+
+        Ok(Mac { algorithm: self, state })
+    }
+
+    /// Authenticate everything read from `reader` with `key`.
+    pub fn mac_reader(self, key: &[u8], reader: impl Read) -> std::io::Result<Vec<u8>> {
+        let mut mac = self.mac(key).map_err(std::io::Error::other)?;
+
+        crate::read_chunks(reader, |chunk| mac.update(chunk))?;
+
+        Ok(mac.finalize())
+    }
+}
+
+enum State {
+    HmacSha256(Box<Hmac<sha2::Sha256>>),
+    HmacSha512(Box<Hmac<sha2::Sha512>>),
+    Blake3(Box<blake3::Hasher>),
+    Kmac(Box<Kmac>)
+}
+
+/// The incremental state of a message authentication code.
+pub struct Mac {
+    algorithm: MacAlgorithm,
+    state: State
+}
+
+impl Mac {
+    pub fn algorithm(&self) -> MacAlgorithm {
+        self.algorithm
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            State::HmacSha256(mac) => mac.update(data),
+            State::HmacSha512(mac) => mac.update(data),
+            State::Blake3(hasher) => { hasher.update(data); }
+            State::Kmac(kmac) => kmac.update(data)
+        }
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        match self.state {
+            State::HmacSha256(mac) => mac.finalize().into_bytes().to_vec(),
+            State::HmacSha512(mac) => mac.finalize().into_bytes().to_vec(),
+            State::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+            State::Kmac(kmac) => {
+                let mut output = vec![0u8; self.algorithm.output_length()];
+
+                kmac.finalize(&mut output);
+
+                output
+            }
+        }
+    }
+
+    /// Whether the code of the message is `expected`, compared in constant time.
+    pub fn verify(self, expected: &[u8]) -> bool {
+        verify(&self.finalize(), expected)
+    }
+}
+
+/// Compare two codes in time which depends only on their lengths.
+pub fn verify(computed: &[u8], expected: &[u8]) -> bool {
+    computed.ct_eq(expected).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MacAlgorithm, MacError};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn mac(algorithm: MacAlgorithm, key: &[u8], data: &[u8]) -> String {
+        let mut mac = algorithm.mac(key).unwrap();
+
+        mac.update(data);
+
+        hex(&mac.finalize())
+    }
+
+    #[test]
+    fn can_compute_hmacs() {
+        // Test case 2 of RFC 4231.
+        let data = b"what do ya want for nothing?";
+
+        assert_eq!(mac(MacAlgorithm::HmacSha256, b"Jefe", data), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        assert_eq!(
+            mac(MacAlgorithm::HmacSha512, b"Jefe", data),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+    }
+
+    #[test]
+    fn can_compute_kmacs() {
+        // Sample 1 of the NIST SP 800-185 examples.
+        let key: Vec<u8> = (0x40..=0x5f).collect();
+
+        assert_eq!(mac(MacAlgorithm::Kmac128, &key, &[0, 1, 2, 3]), "e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e");
+        assert_eq!(MacAlgorithm::Kmac256.mac(&key).unwrap().finalize().len(), 64);
+    }
+
+    #[test]
+    fn keyed_blake3_requires_a_32_byte_key() {
+        let key = [7u8; 32];
+
+        assert_eq!(mac(MacAlgorithm::Blake3Keyed, &key, b"abc"), hex(blake3::keyed_hash(&key, b"abc").as_bytes()));
+        assert!(matches!(MacAlgorithm::Blake3Keyed.mac(&[7u8; 16]), Err(MacError::KeyLength { expected: 32, length: 16, .. })));
+    }
+
+    #[test]
+    fn rejects_empty_keys() {
+        assert!(matches!(MacAlgorithm::HmacSha256.mac(b""), Err(MacError::EmptyKey)));
+    }
+
+    #[test]
+    fn verifies_codes() {
+        let code = MacAlgorithm::HmacSha256.mac_reader(b"key", &b"payload"[..]).unwrap();
+        let mut mac = MacAlgorithm::HmacSha256.mac(b"key").unwrap();
+
+        mac.update(b"payload");
+
+        assert!(mac.verify(&code));
+        assert!(!super::verify(&code, &code[..31]));
+        assert!(!super::verify(&code, &[0u8; 32]));
+    }
+}
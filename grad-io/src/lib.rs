@@ -1,3 +1,3 @@
 mod zstd;
 
-pub use zstd::decompress_lines;
+pub use zstd::{DecompressError, decompress_lines, is_compressed};
@@ -10,6 +10,14 @@ pub enum DecompressError {
     FromUtf8(#[from] std::string::FromUtf8Error)
 }
 
+/// The magic number with which every zstd frame begins.
+const MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Whether the data begins with a zstd frame.
+pub fn is_compressed(data: &[u8]) -> bool {
+    data.starts_with(&MAGIC)
+}
+
 #[instrument(skip_all)]
 pub fn decompress_lines(data: &[u8]) -> Result<Vec<String>, DecompressError> {
     let buffer = zstd::stream::decode_all(data)?;
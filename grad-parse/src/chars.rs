@@ -0,0 +1,110 @@
+use std::str::FromStr;
+
+use derive_more::{Constructor, Deref, From, Into};
+use thiserror::Error;
+
+#[derive(std::fmt::Debug, Error)]
+pub enum ParseCharSpecError {
+    #[error("The character specification is empty.")]
+    Empty,
+
+    #[error("The escape sequence at offset {0} is incomplete.")]
+    Incomplete(usize),
+
+    #[error("The range '{1}-{2}' at offset {0} is reversed.")]
+    Reversed(usize, char, char)
+}
+
+/// A set of characters given by a specification such as `a-zA-Z0-9#%`.
+///
+/// Each character stands for itself, and two characters joined by a hyphen stand for every character
+/// between them, inclusive. A hyphen at the start or end of the specification is literal, and a
+/// backslash escapes the character which follows it, so `\-` and `\\` stand for `-` and `\`.
+///
+/// The characters are sorted and deduplicated.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug, Constructor, Deref, From, Into)]
+pub struct CharSpec(Vec<char>);
+
+impl FromStr for CharSpec {
+    type Err = ParseCharSpecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() { return Err(ParseCharSpecError::Empty) }
+
+        // Resolve escape sequences first, remembering which characters were escaped and where.
+        let mut symbols: Vec<(usize, char, bool)> = Vec::with_capacity(s.len());
+        let mut chars = s.char_indices();
+
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '\\' => symbols.push((offset, chars.next().ok_or(ParseCharSpecError::Incomplete(offset))?.1, true)),
+                c => symbols.push((offset, c, false))
+            }
+        }
+
+        let mut buffer = Vec::with_capacity(symbols.len());
+        let mut index = 0;
+
+        while index < symbols.len() {
+            let (offset, start, _) = symbols[index];
+
+            match symbols.get(index + 1..index + 3) {
+                Some(&[(_, '-', false), (_, end, _)]) => {
+                    if start > end { return Err(ParseCharSpecError::Reversed(offset, start, end)) }
+
+                    buffer.extend(start..=end);
+                    index += 3;
+                }
+                _ => {
+                    buffer.push(start);
+                    index += 1;
+                }
+            }
+        }
+
+        buffer.sort();
+        buffer.dedup();
+
+        Ok(CharSpec(buffer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{CharSpec, ParseCharSpecError};
+
+    #[test]
+    fn can_parse_ranges() {
+        let spec = CharSpec::from_str("a-cX-Z0-2").unwrap();
+
+        assert_eq!(spec.as_slice(), &['0', '1', '2', 'X', 'Y', 'Z', 'a', 'b', 'c']);
+    }
+
+    #[test]
+    fn can_parse_literals() {
+        let spec = CharSpec::from_str("#%a#").unwrap();
+
+        assert_eq!(spec.as_slice(), &['#', '%', 'a']);
+    }
+
+    #[test]
+    fn can_parse_literal_hyphens() {
+        assert_eq!(CharSpec::from_str("-a").unwrap().as_slice(), &['-', 'a']);
+        assert_eq!(CharSpec::from_str("a-").unwrap().as_slice(), &['-', 'a']);
+        assert_eq!(CharSpec::from_str(r"a\-c").unwrap().as_slice(), &['-', 'a', 'c']);
+    }
+
+    #[test]
+    fn can_parse_escaped_range_bounds() {
+        assert_eq!(CharSpec::from_str(r"\--/").unwrap().as_slice(), &['-', '.', '/']);
+    }
+
+    #[test]
+    fn rejects_invalid_specifications() {
+        assert!(matches!(CharSpec::from_str(""), Err(ParseCharSpecError::Empty)));
+        assert!(matches!(CharSpec::from_str("z-a"), Err(ParseCharSpecError::Reversed(0, 'z', 'a'))));
+        assert!(matches!(CharSpec::from_str(r"ab\"), Err(ParseCharSpecError::Incomplete(2))));
+    }
+}
@@ -0,0 +1,98 @@
+use std::str::FromStr;
+
+use derive_more::{Constructor, Deref, From, Into};
+use thiserror::Error;
+
+#[derive(std::fmt::Debug, Error)]
+pub enum ParseEscapeError {
+    #[error("The escape sequence at offset {0} is incomplete.")]
+    Incomplete(usize),
+
+    #[error("The escape sequence '\\{1}' at offset {0} is not recognized.")]
+    Unknown(usize, char),
+
+    #[error("The hexadecimal escape sequence at offset {0} is invalid.")]
+    Hex(usize)
+}
+
+/// A byte string in which backslash escape sequences have been interpreted.
+///
+/// The recognized escape sequences are `\0`, `\a`, `\b`, `\t`, `\n`, `\v`, `\f`, `\r`, `\e`, `\\`
+/// and `\xHH`, where `HH` is a pair of hexadecimal digits.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug, Constructor, Deref, From, Into)]
+pub struct Escaped(Vec<u8>);
+
+impl FromStr for Escaped {
+    type Err = ParseEscapeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut buffer = Vec::with_capacity(s.len());
+        let mut chars = s.char_indices();
+
+        while let Some((offset, c)) = chars.next() {
+            if c != '\\' {
+                buffer.extend_from_slice(c.encode_utf8(&mut [0u8; 4]).as_bytes());
+
+                continue;
+            }
+
+            let byte = match chars.next().ok_or(ParseEscapeError::Incomplete(offset))?.1 {
+                '0' => 0x00,
+                'a' => 0x07,
+                'b' => 0x08,
+                't' => b'\t',
+                'n' => b'\n',
+                'v' => 0x0b,
+                'f' => 0x0c,
+                'r' => b'\r',
+                'e' => 0x1b,
+                '\\' => b'\\',
+                'x' => {
+                    let digits: String = chars.by_ref().take(2).map(|(_, c)| c).collect();
+
+                    if digits.len() != 2 { return Err(ParseEscapeError::Hex(offset)) }
+
+                    u8::from_str_radix(&digits, 16).map_err(|_| ParseEscapeError::Hex(offset))?
+                }
+                other => return Err(ParseEscapeError::Unknown(offset, other))
+            };
+
+            buffer.push(byte);
+        }
+
+        Ok(Escaped(buffer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Escaped;
+
+    #[test]
+    fn can_parse_escape_sequences() {
+        let escaped = Escaped::from_str(r"a\tb\0c\\d\x7f\n").unwrap();
+
+        assert_eq!(escaped.as_slice(), b"a\tb\0c\\d\x7f\n");
+    }
+
+    #[test]
+    fn can_parse_unicode() {
+        let escaped = Escaped::from_str("→").unwrap();
+
+        assert_eq!(escaped.as_slice(), "→".as_bytes());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_trailing_backslash() {
+        Escaped::from_str(r"abc\").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_unknown_escape() {
+        Escaped::from_str(r"\q").unwrap();
+    }
+}
@@ -0,0 +1,107 @@
+use std::num::ParseIntError;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use data_encoding::{BASE64_NOPAD, BASE64URL_NOPAD, HEXLOWER_PERMISSIVE};
+use thiserror::Error;
+
+#[derive(std::fmt::Debug, Error)]
+pub enum ParseKeyError {
+    #[error("The key must not be empty.")]
+    Empty,
+
+    #[error("The key is neither hexadecimal nor Base64.")]
+    Decode(#[source] data_encoding::DecodeError),
+
+    #[error("The length of a random key must be a positive number of bytes.")]
+    Length,
+
+    #[error(transparent)]
+    ParseInt(#[from] ParseIntError)
+}
+
+/// The secret key of a message authentication code.
+///
+/// Keys may be given in one of the following formats:
+///
+/// 1. Hexadecimal digits, optionally prefixed with `0x`.
+///
+/// 2. Base64 with the standard or URL-safe alphabet, with or without padding.
+///
+/// 3. `@` followed by the path of a file whose bytes are the key.
+///
+/// 4. `random:` followed by a number of bytes, to generate a fresh key.
+///
+/// A string which is valid as both hexadecimal and Base64 is read as hexadecimal.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Key {
+    Bytes(Vec<u8>),
+    File(PathBuf),
+    Random(usize)
+}
+
+impl FromStr for Key {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() { return Err(ParseKeyError::Empty) }
+
+        if let Some(path) = s.strip_prefix('@') { return Ok(Key::File(PathBuf::from(path))) }
+
+        if let Some(length) = s.strip_prefix("random:") {
+            return match usize::from_str(length)? {
+                0 => Err(ParseKeyError::Length),
+                length => Ok(Key::Random(length))
+            }
+        }
+
+        let prefixed = s.strip_prefix("0x");
+        let digits = prefixed.unwrap_or(s);
+
+        if prefixed.is_some() || (digits.len().is_multiple_of(2) && digits.chars().all(|c| c.is_ascii_hexdigit())) {
+            return HEXLOWER_PERMISSIVE.decode(digits.as_bytes()).map(Key::Bytes).map_err(ParseKeyError::Decode)
+        }
+
+        let unpadded = s.trim_end_matches('=');
+
+        BASE64_NOPAD.decode(unpadded.as_bytes())
+            .or_else(|_| BASE64URL_NOPAD.decode(unpadded.as_bytes()))
+            .map(Key::Bytes)
+            .map_err(ParseKeyError::Decode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use super::{Key, ParseKeyError};
+
+    #[test]
+    fn can_parse_hexadecimal_keys() {
+        assert_eq!(Key::from_str("DEADbeef").unwrap(), Key::Bytes(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(Key::from_str("0x0102").unwrap(), Key::Bytes(vec![1, 2]));
+    }
+
+    #[test]
+    fn can_parse_base64_keys() {
+        assert_eq!(Key::from_str("SmVmZQ==").unwrap(), Key::Bytes(b"Jefe".to_vec()));
+        assert_eq!(Key::from_str("SmVmZQ").unwrap(), Key::Bytes(b"Jefe".to_vec()));
+        assert_eq!(Key::from_str("-_8").unwrap(), Key::Bytes(vec![0xfb, 0xff]));
+    }
+
+    #[test]
+    fn can_parse_files_and_random_keys() {
+        assert_eq!(Key::from_str("@secret.key").unwrap(), Key::File(PathBuf::from("secret.key")));
+        assert_eq!(Key::from_str("random:32").unwrap(), Key::Random(32));
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(matches!(Key::from_str(""), Err(ParseKeyError::Empty)));
+        assert!(matches!(Key::from_str("random:0"), Err(ParseKeyError::Length)));
+        assert!(matches!(Key::from_str("random:many"), Err(ParseKeyError::ParseInt(..))));
+        assert!(matches!(Key::from_str("not a key!"), Err(ParseKeyError::Decode(..))));
+    }
+}
@@ -1,3 +1,11 @@
 //! Implements parsing logic for various types of data.
 
+pub mod chars;
+pub mod escape;
+pub mod key;
+pub mod namespace;
 pub mod range;
+pub mod seed;
+pub mod size;
+pub mod template;
+pub mod timestamp;
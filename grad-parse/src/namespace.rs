@@ -0,0 +1,87 @@
+use std::str::FromStr;
+
+use data_encoding::HEXLOWER_PERMISSIVE;
+use derive_more::{Constructor, From, Into};
+use thiserror::Error;
+
+#[derive(std::fmt::Debug, Error)]
+pub enum ParseNamespaceError {
+    #[error("Expected dns, url, oid, x500 or a UUID, but found {0} hexadecimal digits.")]
+    Length(usize),
+
+    #[error(transparent)]
+    Decode(#[from] data_encoding::DecodeError)
+}
+
+/// The namespace of a name-based UUID, as defined by RFC 9562.
+///
+/// Acceptable formats are one of the predefined namespaces `dns`, `url`, `oid` and `x500`, or any
+/// UUID in hyphenated, simple, braced or URN form.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Constructor, From, Into)]
+pub struct Namespace([u8; 16]);
+
+impl Namespace {
+    pub const DNS: Namespace = Namespace(*b"\x6b\xa7\xb8\x10\x9d\xad\x11\xd1\x80\xb4\x00\xc0\x4f\xd4\x30\xc8");
+    pub const URL: Namespace = Namespace(*b"\x6b\xa7\xb8\x11\x9d\xad\x11\xd1\x80\xb4\x00\xc0\x4f\xd4\x30\xc8");
+    pub const OID: Namespace = Namespace(*b"\x6b\xa7\xb8\x12\x9d\xad\x11\xd1\x80\xb4\x00\xc0\x4f\xd4\x30\xc8");
+    pub const X500: Namespace = Namespace(*b"\x6b\xa7\xb8\x14\x9d\xad\x11\xd1\x80\xb4\x00\xc0\x4f\xd4\x30\xc8");
+}
+
+impl FromStr for Namespace {
+    type Err = ParseNamespaceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "dns" => return Ok(Namespace::DNS),
+            "url" => return Ok(Namespace::URL),
+            "oid" => return Ok(Namespace::OID),
+            "x500" => return Ok(Namespace::X500),
+            _ => ()
+        }
+
+        let s = s.strip_prefix("urn:uuid:").unwrap_or(s);
+        let s = s.strip_prefix('{').and_then(|s| s.strip_suffix('}')).unwrap_or(s);
+        let digits: String = s.chars().filter(|&c| c != '-').collect();
+
+        if digits.len() != 32 { return Err(ParseNamespaceError::Length(digits.len())) }
+
+        let mut bytes = [0u8; 16];
+
+        HEXLOWER_PERMISSIVE.decode_mut(digits.as_bytes(), &mut bytes).map_err(|error| error.error)?;
+
+        Ok(Namespace(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{Namespace, ParseNamespaceError};
+
+    #[test]
+    fn can_parse_predefined_namespaces() {
+        assert_eq!(Namespace::from_str("dns").unwrap(), Namespace::DNS);
+        assert_eq!(Namespace::from_str("X500").unwrap(), Namespace::X500);
+    }
+
+    #[test]
+    fn can_parse_uuid_forms() {
+        let forms = [
+            "6ba7b811-9dad-11d1-80b4-00c04fd430c8",
+            "6BA7B8119DAD11D180B400C04FD430C8",
+            "{6ba7b811-9dad-11d1-80b4-00c04fd430c8}",
+            "urn:uuid:6ba7b811-9dad-11d1-80b4-00c04fd430c8"
+        ];
+
+        for form in forms {
+            assert_eq!(Namespace::from_str(form).unwrap(), Namespace::URL);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_namespace() {
+        assert!(matches!(Namespace::from_str("example"), Err(ParseNamespaceError::Length(..))));
+        assert!(matches!(Namespace::from_str("6ba7b811-9dad-11d1-80b4-00c04fd430zz"), Err(ParseNamespaceError::Decode(..))));
+    }
+}
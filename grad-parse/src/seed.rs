@@ -0,0 +1,95 @@
+use std::str::FromStr;
+
+use data_encoding::HEXLOWER_PERMISSIVE;
+use derive_more::{Constructor, From, Into};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+#[derive(std::fmt::Debug, Error)]
+pub enum ParseSeedError {
+    #[error("The seed must not be empty.")]
+    Empty,
+
+    #[error("The hexadecimal seed '{0}' must encode exactly 32 bytes.")]
+    Length(String),
+
+    #[error(transparent)]
+    Decode(#[from] data_encoding::DecodeError)
+}
+
+/// A 256-bit seed for a deterministic random number generator.
+///
+/// Seeds may be parsed from one of the following formats:
+///
+/// 1. Sixty-four hexadecimal digits, optionally prefixed with `0x`, which are used verbatim.
+///
+/// 2. An unsigned 64-bit integer, whose little-endian bytes are hashed with SHA-256.
+///
+/// 3. Any other string, which is treated as a passphrase and hashed with SHA-256.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Constructor, From, Into)]
+pub struct Seed([u8; 32]);
+
+impl FromStr for Seed {
+    type Err = ParseSeedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() { return Err(ParseSeedError::Empty) }
+
+        let prefixed = s.strip_prefix("0x");
+        let digits = prefixed.unwrap_or(s);
+
+        if prefixed.is_some() || (digits.len() == 64 && digits.chars().all(|c| c.is_ascii_hexdigit())) {
+            let bytes = HEXLOWER_PERMISSIVE.decode(digits.as_bytes())?;
+            let bytes: [u8; 32] = bytes.try_into().map_err(|_| ParseSeedError::Length(s.to_string()))?;
+
+            return Ok(Seed(bytes));
+        }
+
+        let digest = match u64::from_str(s) {
+            Ok(integer) => Sha256::digest(integer.to_le_bytes()),
+            Err(..) => Sha256::digest(s.as_bytes())
+        };
+
+        Ok(Seed(digest.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Seed;
+
+    #[test]
+    fn can_parse_hex_seed() {
+        let string = "00".repeat(31) + "ff";
+        let seed = Seed::from_str(&string).unwrap();
+        let mut bytes = [0u8; 32];
+
+        bytes[31] = 0xff;
+
+        assert_eq!(<[u8; 32]>::from(seed), bytes);
+        assert_eq!(Seed::from_str(&format!("0x{}", string)).unwrap(), seed);
+    }
+
+    #[test]
+    fn integer_and_passphrase_seeds_differ() {
+        let integer = Seed::from_str("42").unwrap();
+        let passphrase = Seed::from_str("forty-two").unwrap();
+
+        assert_eq!(integer, Seed::from_str("42").unwrap());
+        assert_ne!(integer, passphrase);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_short_hex_seed() {
+        Seed::from_str("0xdeadbeef").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_empty_seed() {
+        Seed::from_str("").unwrap();
+    }
+}
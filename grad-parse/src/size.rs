@@ -0,0 +1,96 @@
+use std::num::ParseIntError;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use thiserror::Error;
+use derive_more::{Constructor, Display, From, Into};
+
+#[derive(std::fmt::Debug, Error)]
+pub enum ParseSizeError {
+    #[error("The haystack '{0}' did not match the pattern '{1}'.")]
+    NoMatch(String, String),
+
+    #[error("The size '{0}' is too large.")]
+    Overflow(String),
+
+    #[error(transparent)]
+    ParseInt(#[from] ParseIntError)
+}
+
+/// A quantity of bytes, optionally written with a unit suffix.
+///
+/// Suffixes follow the conventions of GNU coreutils: `K`, `M`, `G`, `T`, `P` and `E` and their
+/// `KiB`-style equivalents denote powers of 1024, whereas `KB`-style suffixes denote powers of 1000.
+/// Suffixes are case-insensitive, and a trailing `B` alone denotes bytes.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, Constructor, From, Into)]
+pub struct Size(u64);
+
+impl FromStr for Size {
+    type Err = ParseSizeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^(?<value>\d+)\s*(?<unit>[kmgtpe]?)(?<binary>i?)(?<suffix>b?)$").unwrap());
+
+        let haystack = s.trim();
+        let captures = RE.captures(haystack).ok_or(ParseSizeError::NoMatch(haystack.to_string(), RE.to_string()))?;
+        let value = u64::from_str(&captures["value"])?;
+        let unit = captures["unit"].to_ascii_lowercase();
+        let decimal = captures["binary"].is_empty() && ! captures["suffix"].is_empty();
+
+        let exponent = match unit.as_str() {
+            "k" => 1,
+            "m" => 2,
+            "g" => 3,
+            "t" => 4,
+            "p" => 5,
+            "e" => 6,
+            _ if ! captures["binary"].is_empty() => return Err(ParseSizeError::NoMatch(haystack.to_string(), RE.to_string())),
+            _ => 0
+        };
+
+        let base: u64 = if decimal { 1000 } else { 1024 };
+
+        base.checked_pow(exponent)
+            .and_then(|multiplier| value.checked_mul(multiplier))
+            .map(Size)
+            .ok_or(ParseSizeError::Overflow(haystack.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Size;
+
+    #[test]
+    fn can_parse_plain_size() {
+        assert_eq!(u64::from(Size::from_str("50000000000").unwrap()), 50_000_000_000);
+        assert_eq!(u64::from(Size::from_str("256B").unwrap()), 256);
+    }
+
+    #[test]
+    fn can_parse_binary_suffixes() {
+        assert_eq!(u64::from(Size::from_str("4GiB").unwrap()), 4 << 30);
+        assert_eq!(u64::from(Size::from_str("1T").unwrap()), 1 << 40);
+        assert_eq!(u64::from(Size::from_str("2k").unwrap()), 2048);
+    }
+
+    #[test]
+    fn can_parse_decimal_suffixes() {
+        assert_eq!(u64::from(Size::from_str("3MB").unwrap()), 3_000_000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_overflow() {
+        Size::from_str("32EiB").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_unknown_suffix() {
+        Size::from_str("4XB").unwrap();
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use derive_more::{Deref, Into};
+use grad_rand::pronounceable::Slot;
+use thiserror::Error;
+
+#[derive(std::fmt::Debug, Error)]
+pub enum ParseTemplateError {
+    #[error("The template is empty.")]
+    Empty,
+
+    #[error("The character '{1}' at offset {0} is not a template slot; expected one of C, V, D or S.")]
+    Unknown(usize, char)
+}
+
+/// A sequence of slots such as `CVCCVC`, in which `C` stands for a consonant, `V` for a vowel, `D` for
+/// a digit and `S` for a symbol. Slots are case-insensitive.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Deref, Into)]
+pub struct Template(Vec<Slot>);
+
+impl FromStr for Template {
+    type Err = ParseTemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() { return Err(ParseTemplateError::Empty) }
+
+        s.char_indices()
+            .map(|(offset, c)| match c.to_ascii_uppercase() {
+                'C' => Ok(Slot::Consonant),
+                'V' => Ok(Slot::Vowel),
+                'D' => Ok(Slot::Digit),
+                'S' => Ok(Slot::Symbol),
+                _ => Err(ParseTemplateError::Unknown(offset, c))
+            })
+            .collect::<Result<_, _>>()
+            .map(Template)
+    }
+}
+
+impl Display for Template {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.iter()
+            .map(|slot| match slot {
+                Slot::Consonant => 'C',
+                Slot::Vowel => 'V',
+                Slot::Digit => 'D',
+                Slot::Symbol => 'S'
+            })
+            .try_for_each(|c| write!(f, "{}", c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use grad_rand::pronounceable::Slot;
+
+    use super::{ParseTemplateError, Template};
+
+    #[test]
+    fn can_parse_template() {
+        let template = Template::from_str("CVcvDS").unwrap();
+
+        assert_eq!(template.as_slice(), &[Slot::Consonant, Slot::Vowel, Slot::Consonant, Slot::Vowel, Slot::Digit, Slot::Symbol]);
+        assert_eq!(template.to_string(), "CVCVDS");
+    }
+
+    #[test]
+    fn rejects_invalid_template() {
+        assert!(matches!(Template::from_str(""), Err(ParseTemplateError::Empty)));
+        assert!(matches!(Template::from_str("CVX"), Err(ParseTemplateError::Unknown(2, 'X'))));
+    }
+}
@@ -0,0 +1,81 @@
+use std::num::ParseIntError;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use chrono::DateTime;
+use derive_more::{Constructor, From, Into};
+use regex::Regex;
+use thiserror::Error;
+
+static REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?<value>-?[0-9]+)(?<unit>s|ms)?$").unwrap());
+
+#[derive(std::fmt::Debug, Error)]
+pub enum ParseTimestampError {
+    #[error("Expected an RFC 3339 timestamp or a Unix timestamp in s or ms.")]
+    NoMatch(#[source] chrono::ParseError),
+
+    #[error("The timestamp is out of range.")]
+    Overflow,
+
+    #[error(transparent)]
+    ParseInt(#[from] ParseIntError)
+}
+
+/// An instant in milliseconds since the Unix epoch.
+///
+/// Acceptable formats are an RFC 3339 timestamp such as `2024-01-01T00:00:00Z`, or a Unix timestamp
+/// in milliseconds, optionally suffixed with its unit: `1704067200000`, `1704067200000ms` or
+/// `1704067200s`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Constructor, From, Into)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    pub fn millis(self) -> i64 {
+        self.0
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = ParseTimestampError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(captures) = REGEX.captures(s.trim()) else {
+            let datetime = DateTime::parse_from_rfc3339(s.trim()).map_err(ParseTimestampError::NoMatch)?;
+
+            return Ok(Timestamp(datetime.timestamp_millis()))
+        };
+
+        let value: i64 = captures["value"].parse()?;
+
+        match captures.name("unit").map(|unit| unit.as_str()) {
+            Some("s") => value.checked_mul(1000).map(Timestamp).ok_or(ParseTimestampError::Overflow),
+            _ => Ok(Timestamp(value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{ParseTimestampError, Timestamp};
+
+    #[test]
+    fn can_parse_unix_timestamps() {
+        assert_eq!(Timestamp::from_str("1704067200000").unwrap().millis(), 1_704_067_200_000);
+        assert_eq!(Timestamp::from_str("1704067200000ms").unwrap().millis(), 1_704_067_200_000);
+        assert_eq!(Timestamp::from_str("1704067200s").unwrap().millis(), 1_704_067_200_000);
+    }
+
+    #[test]
+    fn can_parse_rfc3339() {
+        assert_eq!(Timestamp::from_str("2024-01-01T00:00:00Z").unwrap().millis(), 1_704_067_200_000);
+        assert_eq!(Timestamp::from_str("2024-01-01T01:00:00.5+01:00").unwrap().millis(), 1_704_067_200_500);
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        assert!(matches!(Timestamp::from_str("yesterday"), Err(ParseTimestampError::NoMatch(..))));
+        assert!(matches!(Timestamp::from_str("9223372036854775807s"), Err(ParseTimestampError::Overflow)));
+    }
+}
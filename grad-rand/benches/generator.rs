@@ -0,0 +1,46 @@
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use grad_rand::byte::random_hex;
+use grad_rand::rng::Backend;
+
+const ITEMS: u64 = 10000;
+
+/// Compare seeding a new source for every item against reusing a single generator.
+fn short_tokens(c: &mut Criterion) {
+    let mut group = c.benchmark_group("short_tokens");
+
+    group.throughput(Throughput::Elements(ITEMS));
+
+    for seed in [None, Some([0u8; 32])] {
+        let label = if seed.is_some() { "seeded" } else { "unseeded" };
+
+        group.bench_with_input(BenchmarkId::new("fresh_source", label), &seed, |b, &seed| {
+            b.iter(|| {
+                for index in 0..ITEMS {
+                    let mut source = Backend::default().source(seed, index).unwrap();
+
+                    black_box(random_hex(&mut source, false, 16));
+                }
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("reused_generator", label), &seed, |b, &seed| {
+            b.iter(|| {
+                let mut generator = Backend::default().generator(seed).unwrap();
+
+                for index in 0..ITEMS {
+                    generator.select(index);
+
+                    black_box(random_hex(&mut generator, false, 16));
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, short_tokens);
+criterion_main!(benches);
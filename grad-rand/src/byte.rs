@@ -1,54 +1,93 @@
-use rand::prelude::*;
-use rand_chacha::ChaChaRng;
+use std::io::{self, Write};
+
 use data_encoding::{HEXLOWER, HEXUPPER, BASE64, BASE64URL};
+use grad_encoding::{EncodeError, Encoding};
+
+use crate::rng::RandomSource;
 
-pub fn random_byte(length: usize)-> Vec<u8> {
+/// The number of bytes generated at a time by [`write_random_byte`].
+///
+/// This is a multiple of the word size of every backend, so streamed output is identical to the
+/// output of [`random_byte`] for the same source.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+pub fn random_byte<R: RandomSource + ?Sized>(rng: &mut R, length: usize)-> Vec<u8> {
     if length == 0 { return Vec::new() }
 
-    let rng = ChaChaRng::from_rng(&mut rand::rng());
+    let mut buffer = vec![0u8; length];
+
+    rng.fill_bytes(&mut buffer);
+
+    buffer
+}
+
+/// Write `length` random bytes to `writer` in fixed-size chunks, without buffering the entire output.
+///
+/// The source is checked before each chunk is written, so no bytes are written after it fails.
+pub fn write_random_byte<R, W>(rng: &mut R, writer: &mut W, length: u64) -> io::Result<()>
+where
+    R: RandomSource + ?Sized, W: Write + ?Sized
+{
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut remaining = length;
 
-    rng.random_iter().take(length).collect()
+    while remaining > 0 {
+        let size = remaining.min(CHUNK_SIZE as u64) as usize;
+
+        rng.fill_bytes(&mut buffer[..size]);
+        rng.check().map_err(io::Error::other)?;
+        writer.write_all(&buffer[..size])?;
+
+        remaining -= size as u64;
+    }
+
+    Ok(())
 }
 
-pub fn random_hex(uppercase: bool, length: usize) -> Vec<u8> {
+pub fn random_hex<R: RandomSource + ?Sized>(rng: &mut R, uppercase: bool, length: usize) -> Vec<u8> {
     if length == 0 { return Vec::new() }
 
-    let rng = ChaChaRng::from_rng(&mut rand::rng());
-    let buffer: Vec<u8> = rng.random_iter().take(length).collect();
+    let buffer = random_byte(rng, length);
 
     if uppercase { HEXUPPER.encode(&buffer).into_bytes() }
     else { HEXLOWER.encode(&buffer).into_bytes() }
 }
 
-pub fn random_base64(url: bool, length: usize) -> Vec<u8> {
+pub fn random_base64<R: RandomSource + ?Sized>(rng: &mut R, url: bool, length: usize) -> Vec<u8> {
     if length == 0 { return Vec::new() }
 
-    let rng = ChaChaRng::from_rng(&mut rand::rng());
-    let buffer: Vec<u8> = rng.random_iter().take(length).collect();
+    let buffer = random_byte(rng, length);
 
     if url { BASE64URL.encode(&buffer).into_bytes() }
     else { BASE64.encode(&buffer).into_bytes() }
 }
 
+/// Generate `length` random bytes and encode them with `encoding`.
+pub fn random_encoded<R: RandomSource + ?Sized>(rng: &mut R, encoding: &Encoding, length: usize) -> Result<Vec<u8>, EncodeError> {
+    encoding.encode(&random_byte(rng, length))
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::rng::rng;
+
     #[test]
     fn can_generate_one_kilobyte() {
-        let bytes = super::random_byte(1024);
+        let bytes = super::random_byte(&mut rng(None, 0), 1024);
 
         assert_eq!(bytes.len(), 1024)
     }
 
     #[test]
     fn can_generate_zero_bytes() {
-        let bytes = super::random_byte(0);
+        let bytes = super::random_byte(&mut rng(None, 0), 0);
 
         assert_eq!(bytes.len(), 0)
     }
 
     #[test]
     fn can_generate_zero_bytes_as_hex() {
-        let bytes = super::random_hex(false, 0);
+        let bytes = super::random_hex(&mut rng(None, 0), false, 0);
 
         assert_eq!(bytes.len(), 0)
     }
@@ -56,7 +95,7 @@ mod tests {
     #[test]
     fn can_generate_one_kilobyte_as_hex() {
         let length: usize = 1024;
-        let bytes = super::random_hex(false, length);
+        let bytes = super::random_hex(&mut rng(None, 0), false, length);
         let string = std::str::from_utf8(&bytes).unwrap();
 
         assert_eq!(string.chars().count(), length * 2)
@@ -64,16 +103,42 @@ mod tests {
 
     #[test]
     fn can_generate_zero_bytes_as_base64() {
-        let bytes = super::random_base64(false, 0);
+        let bytes = super::random_base64(&mut rng(None, 0), false, 0);
 
         assert_eq!(bytes.len(), 0)
     }
 
     #[test]
     fn can_generate_one_kilobyte_as_base64() {
-        let bytes = super::random_base64(false, 1024);
+        let bytes = super::random_base64(&mut rng(None, 0), false, 1024);
         let string = std::str::from_utf8(&bytes).unwrap();
 
         assert_eq!(string.chars().count(), 1368)
     }
+
+    #[test]
+    fn encoded_bytes_match_hex() {
+        let seed = Some([1u8; 32]);
+        let encoded = super::random_encoded(&mut rng(seed, 0), &grad_encoding::Encoding::Hex, 32).unwrap();
+
+        assert_eq!(encoded, super::random_hex(&mut rng(seed, 0), false, 32));
+    }
+
+    #[test]
+    fn streamed_bytes_match_buffered_bytes() {
+        let seed = Some([1u8; 32]);
+        let length = super::CHUNK_SIZE * 2 + 3;
+        let mut buffer = Vec::new();
+
+        super::write_random_byte(&mut rng(seed, 0), &mut buffer, length as u64).unwrap();
+
+        assert_eq!(buffer, super::random_byte(&mut rng(seed, 0), length));
+    }
+
+    #[test]
+    fn seeded_bytes_are_reproducible() {
+        let seed = Some([1u8; 32]);
+
+        assert_eq!(super::random_byte(&mut rng(seed, 0), 64), super::random_byte(&mut rng(seed, 0), 64));
+    }
 }
@@ -0,0 +1,75 @@
+//! Entropy of outputs composed of symbols chosen uniformly at random.
+
+use thiserror::Error;
+
+/// The greatest number of symbols which [`length_for_entropy`] will derive.
+pub const MAX_LENGTH: usize = 1 << 20;
+
+#[derive(Debug, Error)]
+pub enum EntropyError {
+    #[error("The minimum entropy must be a finite number of bits, not {0}.")]
+    NotFinite(f64),
+
+    #[error("An alphabet of {alphabet} symbols cannot provide {bits} bits of entropy.")]
+    Alphabet { alphabet: usize, bits: f64 },
+
+    #[error("{bits} bits of entropy would need an output of more than {MAX_LENGTH} symbols.")]
+    TooLong { bits: f64 }
+}
+
+/// The entropy of a single symbol chosen uniformly at random from `alphabet` symbols, in bits.
+pub fn bits_per_symbol(alphabet: usize) -> f64 {
+    (alphabet as f64).log2()
+}
+
+/// The entropy of `length` symbols, each chosen uniformly at random from `alphabet` symbols, in bits.
+pub fn entropy(alphabet: usize, length: usize) -> f64 {
+    length as f64 * bits_per_symbol(alphabet)
+}
+
+/// The smallest number of symbols, each chosen uniformly at random from `alphabet` symbols, which
+/// carries at least `bits` of entropy.
+///
+/// Fails if no number of symbols suffices, which is the case for alphabets of fewer than two
+/// symbols, or if more than [`MAX_LENGTH`] symbols would be needed.
+pub fn length_for_entropy(alphabet: usize, bits: f64) -> Result<usize, EntropyError> {
+    if !bits.is_finite() { return Err(EntropyError::NotFinite(bits)) }
+    if bits <= 0.0 { return Ok(0) }
+    if alphabet < 2 { return Err(EntropyError::Alphabet { alphabet, bits }) }
+
+    let length = (bits / bits_per_symbol(alphabet)).ceil();
+
+    if length > MAX_LENGTH as f64 { return Err(EntropyError::TooLong { bits }) }
+
+    let length = length as usize;
+
+    // Guard against rounding errors pushing an exact quotient up to the next integer.
+    if length > 0 && entropy(alphabet, length - 1) >= bits { Ok(length - 1) } else { Ok(length) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntropyError;
+
+    #[test]
+    fn can_compute_entropy() {
+        assert_eq!(super::entropy(256, 16), 128.0);
+        assert_eq!(super::entropy(2048, 6), 66.0);
+    }
+
+    #[test]
+    fn can_compute_length_for_entropy() {
+        assert_eq!(super::length_for_entropy(256, 128.0).unwrap(), 16);
+        assert_eq!(super::length_for_entropy(10, 128.0).unwrap(), 39);
+        assert_eq!(super::length_for_entropy(2048, 128.0).unwrap(), 12);
+        assert!(matches!(super::length_for_entropy(1, 128.0), Err(EntropyError::Alphabet { .. })));
+    }
+
+    #[test]
+    fn rejects_unreachable_entropy() {
+        assert!(matches!(super::length_for_entropy(256, f64::INFINITY), Err(EntropyError::NotFinite(..))));
+        assert!(matches!(super::length_for_entropy(256, f64::NAN), Err(EntropyError::NotFinite(..))));
+        assert!(matches!(super::length_for_entropy(256, 1e300), Err(EntropyError::TooLong { .. })));
+        assert_eq!(super::length_for_entropy(2, super::MAX_LENGTH as f64).unwrap(), super::MAX_LENGTH);
+    }
+}
@@ -0,0 +1,95 @@
+use rand::RngCore;
+
+use crate::rng::{Backend, RandomSource, SourceError};
+
+/// A reusable handle to a random source from which many items are generated.
+///
+/// Creating a source is far more expensive than drawing a few bytes from it, so a generator should
+/// be created once per thread and reused for every item that thread produces. Seeded generators are
+/// repositioned for each item with [`Generator::select`], so reuse does not affect reproducibility.
+pub struct Generator {
+    source: Box<dyn RandomSource>,
+    seeded: bool
+}
+
+impl Generator {
+    /// Wrap a random source which is read sequentially.
+    pub fn new<S: RandomSource + 'static>(source: S) -> Self {
+        Self { source: Box::new(source), seeded: false }
+    }
+
+    /// Prepare the generator to produce the item at `index` of a batch.
+    ///
+    /// Seeded generators seek to the stream numbered `index`. Unseeded generators simply continue.
+    pub fn select(&mut self, index: u64) {
+        if self.seeded { self.source.seek(index) }
+    }
+}
+
+impl RngCore for Generator {
+    fn next_u32(&mut self) -> u32 {
+        self.source.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.source.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.source.fill_bytes(dest)
+    }
+}
+
+impl RandomSource for Generator {
+    fn name(&self) -> &'static str {
+        self.source.name()
+    }
+
+    fn seek(&mut self, stream: u64) {
+        self.source.seek(stream)
+    }
+
+    fn check(&mut self) -> Result<(), SourceError> {
+        self.source.check()
+    }
+}
+
+impl Backend {
+    /// Create a reusable generator using this backend.
+    pub fn generator(self, seed: Option<[u8; 32]>) -> Result<Generator, SourceError> {
+        let source = self.source(seed, 0)?;
+
+        Ok(Generator { source, seeded: seed.is_some() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::byte::random_byte;
+    use crate::rng::Backend;
+
+    #[test]
+    fn reused_seeded_generator_matches_fresh_sources() {
+        let seed = Some([9u8; 32]);
+        let mut generator = Backend::default().generator(seed).unwrap();
+
+        for index in [3, 0, 7] {
+            generator.select(index);
+
+            assert_eq!(random_byte(&mut generator, 16), random_byte(&mut Backend::default().source(seed, index).unwrap(), 16));
+        }
+    }
+
+    #[test]
+    fn reused_unseeded_generator_does_not_repeat() {
+        let mut generator = Backend::default().generator(None).unwrap();
+
+        generator.select(0);
+        let first = random_byte(&mut generator, 16);
+
+        generator.select(0);
+        let second = random_byte(&mut generator, 16);
+
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,261 @@
+//! Sortable and compact identifiers: ULIDs, KSUIDs, NanoIDs and Snowflake IDs.
+//!
+//! The time-based identifiers are produced by sequences, which fix a timestamp for an entire batch.
+//! ULIDs and Snowflake IDs are derived from their index in the batch, so they increase with their
+//! index no matter the order in which a parallel batch generates them. KSUIDs carry an independent
+//! random payload, so they are only ordered by their timestamps.
+
+use rand::prelude::*;
+use thiserror::Error;
+
+use crate::password::random_password;
+use crate::rng::RandomSource;
+
+/// The alphabet of Crockford's base32, used by ULIDs.
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// The alphabet of the base62 encoding used by KSUIDs.
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// The default alphabet of a NanoID.
+pub const NANOID_ALPHABET: &[char; 64] = &[
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '_', '-'
+];
+
+/// The default length of a NanoID.
+pub const NANOID_LENGTH: usize = 21;
+
+/// The epoch of KSUID timestamps, in seconds since the Unix epoch.
+pub const KSUID_EPOCH: u64 = 1_400_000_000;
+
+/// The epoch of Twitter's Snowflake IDs, in milliseconds since the Unix epoch.
+pub const TWITTER_EPOCH: u64 = 1_288_834_974_657;
+
+/// The largest worker number of a Snowflake ID, which has ten bits.
+pub const MAX_WORKER: u16 = (1 << 10) - 1;
+
+#[derive(Debug, Error)]
+pub enum IdError {
+    #[error("The timestamp {0} ms does not fit in the {1} bits of a {2}.")]
+    Timestamp(u64, u32, &'static str),
+
+    #[error("The timestamp {0} ms precedes the epoch of a {1}.")]
+    BeforeEpoch(u64, &'static str),
+
+    #[error("The worker number {0} exceeds the maximum of {max}.", max = MAX_WORKER)]
+    Worker(u16),
+
+    #[error("The batch runs past the largest timestamp of a {0}.")]
+    Overflow(&'static str)
+}
+
+/// A batch of ULIDs which share a timestamp.
+///
+/// The 80-bit random component of the first ULID is chosen at random with its most significant bit
+/// clear, and each subsequent ULID increments it by one, as the ULID specification prescribes for
+/// monotonic generation within a millisecond.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UlidSequence {
+    timestamp: u64,
+    start: u128
+}
+
+impl UlidSequence {
+    /// Begin a sequence at a Unix timestamp in milliseconds.
+    pub fn new<R: RandomSource + ?Sized>(rng: &mut R, timestamp: u64) -> Result<Self, IdError> {
+        if timestamp >> 48 != 0 { return Err(IdError::Timestamp(timestamp, 48, "ULID")) }
+
+        Ok(Self { timestamp, start: rng.random::<u128>() >> (128 - 79) })
+    }
+
+    /// The ULID at `index` of the sequence.
+    pub fn get(&self, index: u64) -> Vec<u8> {
+        let value = (self.timestamp as u128) << 80 | (self.start + index as u128);
+
+        // The 128 bits are encoded as 26 symbols of five bits, so the first symbol carries three.
+        (0..26)
+            .map(|position| CROCKFORD[(value >> (125 - 5 * position) & 0x1f) as usize])
+            .collect()
+    }
+}
+
+/// A batch of KSUIDs which share a timestamp.
+///
+/// Each KSUID carries a fresh 128-bit random payload, so KSUIDs created in the same second sort in
+/// no particular order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct KsuidSequence {
+    timestamp: u32
+}
+
+impl KsuidSequence {
+    /// Begin a sequence at a Unix timestamp in milliseconds, which KSUIDs truncate to seconds.
+    pub fn new(timestamp: u64) -> Result<Self, IdError> {
+        let seconds = (timestamp / 1000).checked_sub(KSUID_EPOCH).ok_or(IdError::BeforeEpoch(timestamp, "KSUID"))?;
+        let seconds = u32::try_from(seconds).map_err(|_| IdError::Timestamp(timestamp, 32, "KSUID"))?;
+
+        Ok(Self { timestamp: seconds })
+    }
+
+    /// Generate a KSUID with a payload drawn from `rng`.
+    pub fn generate<R: RandomSource + ?Sized>(&self, rng: &mut R) -> Vec<u8> {
+        self.encode(rng.random())
+    }
+
+    fn encode(&self, payload: u128) -> Vec<u8> {
+        let mut bytes = [0u8; 20];
+
+        bytes[..4].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[4..].copy_from_slice(&payload.to_be_bytes());
+
+        base62(&bytes, 27)
+    }
+}
+
+/// Encode `bytes` as a big-endian base62 number, padded with leading zeros to `width` symbols.
+fn base62(bytes: &[u8], width: usize) -> Vec<u8> {
+    let mut digits = bytes.to_vec();
+    let mut output = Vec::with_capacity(width);
+
+    while digits.iter().any(|&digit| digit != 0) {
+        let mut remainder = 0u32;
+
+        for digit in digits.iter_mut() {
+            let value = remainder << 8 | *digit as u32;
+
+            *digit = (value / 62) as u8;
+            remainder = value % 62;
+        }
+
+        output.push(BASE62[remainder as usize]);
+    }
+
+    output.resize(width.max(output.len()), b'0');
+    output.reverse();
+
+    output
+}
+
+/// A batch of Snowflake IDs which share a timestamp and worker.
+///
+/// A Snowflake ID packs 41 bits of milliseconds since an epoch, a 10-bit worker number and a 12-bit
+/// sequence number. Indices beyond the 4096 sequence numbers of a millisecond borrow the following
+/// milliseconds, so IDs remain unique and increasing for batches of any size.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SnowflakeSequence {
+    timestamp: u64,
+    worker: u16
+}
+
+impl SnowflakeSequence {
+    /// Begin a sequence at a Unix timestamp in milliseconds, counted from an epoch in milliseconds.
+    pub fn new(timestamp: u64, epoch: u64, worker: u16) -> Result<Self, IdError> {
+        let elapsed = timestamp.checked_sub(epoch).ok_or(IdError::BeforeEpoch(timestamp, "Snowflake ID"))?;
+
+        if elapsed >> 41 != 0 { return Err(IdError::Timestamp(timestamp, 41, "Snowflake ID")) }
+        if worker > MAX_WORKER { return Err(IdError::Worker(worker)) }
+
+        Ok(Self { timestamp: elapsed, worker })
+    }
+
+    /// The Snowflake ID at `index` of the sequence.
+    ///
+    /// Fails if the index borrows a millisecond beyond the 41 bits of the timestamp.
+    pub fn get(&self, index: u64) -> Result<u64, IdError> {
+        let timestamp = self.timestamp.checked_add(index >> 12)
+            .filter(|timestamp| timestamp >> 41 == 0)
+            .ok_or(IdError::Overflow("Snowflake ID"))?;
+
+        Ok(timestamp << 22 | (self.worker as u64) << 12 | (index & 0xfff))
+    }
+}
+
+/// Generate a NanoID of `length` symbols chosen uniformly from `alphabet`.
+pub fn random_nanoid<R: RandomSource + ?Sized>(rng: &mut R, alphabet: &[char], length: usize) -> Vec<u8> {
+    random_password(rng, alphabet, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::rng;
+
+    use super::{IdError, KsuidSequence, SnowflakeSequence, UlidSequence};
+
+    #[test]
+    fn encodes_ulid_timestamp() {
+        // The example given in the ULID specification.
+        let ulids = UlidSequence { timestamp: 1_469_922_850_259, start: 0 };
+
+        assert_eq!(ulids.get(0), b"01ARZ3NDEK0000000000000000");
+        assert_eq!(ulids.get(33), b"01ARZ3NDEK0000000000000011");
+    }
+
+    #[test]
+    fn ulids_are_monotonic() {
+        let ulids = UlidSequence::new(&mut rng(None, 0), 1_700_000_000_000).unwrap();
+        let batch: Vec<_> = (0..1000).map(|index| ulids.get(index)).collect();
+
+        assert!(batch.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(batch.iter().all(|ulid| ulid.len() == 26));
+    }
+
+    #[test]
+    fn encodes_ksuid() {
+        // The example given in the KSUID documentation.
+        let ksuids = KsuidSequence { timestamp: 107_608_047 };
+
+        assert_eq!(ksuids.encode(0xb5a1cd34b5f99d1154fb6853345c9735), b"0ujtsYcgvSTl8PAuAdqWYSMnLOv");
+    }
+
+    #[test]
+    fn ksuids_have_independent_payloads() {
+        let ksuids = KsuidSequence::new(1_700_000_000_000).unwrap();
+        let mut rng = rng(None, 0);
+        let first = ksuids.generate(&mut rng);
+        let second = ksuids.generate(&mut rng);
+
+        // The payloads differ beyond the low bits which a counter would change.
+        assert_eq!(first[..5], second[..5]);
+        assert_ne!(first[5..20], second[5..20]);
+        assert!(matches!(KsuidSequence::new(0), Err(IdError::BeforeEpoch(..))));
+    }
+
+    #[test]
+    fn ksuids_are_ordered_by_timestamp() {
+        let mut rng = rng(None, 0);
+        let earlier = KsuidSequence::new(1_700_000_000_000).unwrap().generate(&mut rng);
+        let later = KsuidSequence::new(1_700_000_001_000).unwrap().generate(&mut rng);
+
+        assert!(earlier < later);
+    }
+
+    #[test]
+    fn snowflakes_borrow_following_milliseconds() {
+        let snowflakes = SnowflakeSequence::new(super::TWITTER_EPOCH + 5, super::TWITTER_EPOCH, 3).unwrap();
+
+        assert_eq!(snowflakes.get(0).unwrap(), 5 << 22 | 3 << 12);
+        assert_eq!(snowflakes.get(4095).unwrap(), 5 << 22 | 3 << 12 | 4095);
+        assert_eq!(snowflakes.get(4096).unwrap(), 6 << 22 | 3 << 12);
+        assert!(matches!(SnowflakeSequence::new(super::TWITTER_EPOCH, 0, 1024), Err(IdError::Worker(1024))));
+    }
+
+    #[test]
+    fn snowflakes_reject_timestamp_overflow() {
+        let last = (1 << 41) - 1;
+        let snowflakes = SnowflakeSequence::new(super::TWITTER_EPOCH + last, super::TWITTER_EPOCH, 0).unwrap();
+
+        assert_eq!(snowflakes.get(4095).unwrap(), last << 22 | 4095);
+        assert!(matches!(snowflakes.get(4096), Err(IdError::Overflow(..))));
+        assert!(matches!(snowflakes.get(u64::MAX), Err(IdError::Overflow(..))));
+    }
+
+    #[test]
+    fn generates_nanoid() {
+        let bytes = super::random_nanoid(&mut rng(None, 0), super::NANOID_ALPHABET, super::NANOID_LENGTH);
+
+        assert_eq!(bytes.len(), 21);
+        assert!(bytes.iter().all(|&byte| super::NANOID_ALPHABET.contains(&(byte as char))));
+    }
+}
@@ -1,5 +1,14 @@
+pub mod rng;
+pub mod generator;
+pub mod entropy;
 pub mod byte;
+pub mod id;
 pub mod number;
 pub mod digit;
 pub mod password;
 pub mod passphrase;
+pub mod pronounceable;
+pub mod uuid;
+
+#[cfg(test)]
+mod randomness;
@@ -1,20 +1,151 @@
 use std::borrow::Borrow;
+use std::path::Path;
 
 use itertools::Itertools;
 use rand::distr::slice::Choose;
 use rand::prelude::*;
-use rand_chacha::ChaChaRng;
+use thiserror::Error;
 use tracing::{info, instrument};
 
+use grad_io::DecompressError;
+
+use crate::rng::RandomSource;
+
+mod builtin;
+
+pub use builtin::BuiltinList;
+
+#[derive(Debug, Error)]
+pub enum WordListError {
+    #[error("The word list does not contain any words.")]
+    Empty,
+
+    #[error("The word list '{0}' was not included in this build.")]
+    Unavailable(&'static str),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Decompress(#[from] DecompressError),
+
+    #[error(transparent)]
+    FromUtf8(#[from] std::string::FromUtf8Error)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct WordList {
+    name: String,
     inner: Vec<String>
 }
 
 impl WordList {
+    /// Create a word list named `custom` from the given words, discarding empty and duplicate words.
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>, S: Into<String>
+    {
+        let mut inner: Vec<String> = words.into_iter()
+            .map(Into::into)
+            .filter(|word| ! word.is_empty())
+            .collect();
+
+        inner.sort();
+        inner.dedup();
+
+        Self { name: String::from("custom"), inner }
+    }
+
+    /// Load one of the word lists embedded in the program.
+    #[instrument]
+    pub fn builtin(list: BuiltinList) -> Result<Self, WordListError> {
+        let data = list.data().ok_or(WordListError::Unavailable(list.name()))?;
+
+        Ok(Self::from_bytes(data)?.with_name(list.name()))
+    }
+
+    /// Parse a word list containing one word per line.
+    ///
+    /// Lines in the diceware format, in which each word is preceded by its dice roll (e.g.
+    /// `11111\tabacus`), are also accepted, in which case the dice roll is discarded.
+    pub fn from_text<S: AsRef<str>>(text: S) -> Self {
+        Self::from_lines(text.as_ref().lines())
+    }
+
+    /// Parse a word list from plain text or zstd-compressed bytes.
+    ///
+    /// See [`WordList::from_text`] for the accepted formats.
+    #[instrument(skip_all)]
+    pub fn from_bytes(data: &[u8]) -> Result<Self, WordListError> {
+        let words = if grad_io::is_compressed(data) {
+            Self::from_lines(grad_io::decompress_lines(data)?)
+        }
+        else {
+            Self::from_text(String::from_utf8(data.to_vec())?)
+        };
+
+        if words.inner.is_empty() { return Err(WordListError::Empty) }
+
+        info!("Initialized word list with {} words.", words.inner.len());
+
+        Ok(words)
+    }
+
+    /// Read a word list from a file containing plain text or zstd-compressed bytes.
+    ///
+    /// The word list is named after the file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, WordListError> {
+        let path = path.as_ref();
+        let words = Self::from_bytes(&std::fs::read(path)?)?;
+
+        match path.file_name() {
+            Some(name) => Ok(words.with_name(name.to_string_lossy())),
+            None => Ok(words)
+        }
+    }
+
+    /// Rename the word list.
+    pub fn with_name<S: Into<String>>(self, name: S) -> Self {
+        Self { name: name.into(), ..self }
+    }
+
+    fn from_lines<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        Self::new(lines.into_iter().map(|line| strip_dice_roll(line.as_ref().trim()).to_string()))
+    }
+
     pub fn inner(&self) -> &[String] {
         &self.inner
     }
+
+    /// The name of the word list.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The number of distinct words in the word list.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// The entropy contributed by each word chosen uniformly at random from the word list, in bits.
+    pub fn bits_per_word(&self) -> f64 {
+        (self.inner.len() as f64).log2()
+    }
+}
+
+/// Remove the dice roll which precedes a word in the diceware format, if there is one.
+fn strip_dice_roll(line: &str) -> &str {
+    match line.split_once(char::is_whitespace) {
+        Some((roll, word)) if roll.chars().all(|c| ('1'..='6').contains(&c)) => word.trim_start(),
+        _ => line
+    }
 }
 
 impl Default for WordList {
@@ -24,19 +155,18 @@ impl Default for WordList {
 
         info!("Initialized default word list with {} words.", inner.len());
 
-        Self { inner }
+        Self { name: String::from("default"), inner }
     }
 }
 
-pub fn random_passphrase<W, S>(words: W, separator: S, length: usize) -> Vec<u8>
+pub fn random_passphrase<R, W, S>(rng: &mut R, words: W, separator: S, length: usize) -> Vec<u8>
 where
-    W: Borrow<WordList>, S: AsRef<str>
+    R: RandomSource + ?Sized, W: Borrow<WordList>, S: AsRef<str>
 {
     if length == 0 {
         return Vec::new();
     }
 
-    let rng = ChaChaRng::from_rng(&mut rand::rng());
     let distribution = Choose::new(words.borrow().inner()).unwrap();
 
     rng.sample_iter(distribution)
@@ -47,7 +177,9 @@ where
 
 #[cfg(test)]
 mod tests {
-    fn word_count(buffer: &Vec<u8>) -> usize {
+    use crate::rng::rng;
+
+    fn word_count(buffer: &[u8]) -> usize {
         std::str::from_utf8(buffer)
             .unwrap()
             .split(' ')
@@ -57,7 +189,7 @@ mod tests {
     #[test]
     fn can_generate_ten_thousand_word_passphrase() {
         let words = super::WordList::default();
-        let bytes = super::random_passphrase(&words, " ", 10000);
+        let bytes = super::random_passphrase(&mut rng(None, 0), &words, " ", 10000);
         let count = word_count(&bytes);
 
         assert_eq!(count, 10000)
@@ -66,7 +198,7 @@ mod tests {
     #[test]
     fn can_generate_hundred_thousand_word_passphrase() {
         let words = super::WordList::default();
-        let bytes = super::random_passphrase(&words, " ", 100000);
+        let bytes = super::random_passphrase(&mut rng(None, 0), &words, " ", 100000);
         let count = word_count(&bytes);
 
         assert_eq!(count, 100000)
@@ -75,16 +207,63 @@ mod tests {
     #[test]
     fn can_generate_empty_passphrase() {
         let words = super::WordList::default();
-        let bytes = super::random_passphrase(&words, " ", 0);
+        let bytes = super::random_passphrase(&mut rng(None, 0), &words, " ", 0);
 
         assert_eq!(bytes.len(), 0)
     }
 
+    #[test]
+    fn can_parse_plain_word_list() {
+        let words = super::WordList::from_text("banana\napple\n\napple\n");
+
+        assert_eq!(words.inner(), ["apple", "banana"])
+    }
+
+    #[test]
+    fn can_parse_diceware_word_list() {
+        let words = super::WordList::from_text("11111\tabacus\n11112 abdomen\n");
+
+        assert_eq!(words.inner(), ["abacus", "abdomen"])
+    }
+
+    #[test]
+    fn can_parse_compressed_word_list() {
+        let data = zstd::encode_all(&b"11111\tabacus\n11112\tabdomen\n"[..], 0).unwrap();
+        let words = super::WordList::from_bytes(&data).unwrap();
+
+        assert_eq!(words.inner(), ["abacus", "abdomen"])
+    }
+
+    #[test]
+    #[cfg(feature = "bip39-en")]
+    fn can_load_builtin_word_list() {
+        let words = super::WordList::builtin(super::BuiltinList::Bip39English).unwrap();
+
+        assert_eq!(words.name(), "bip39-en");
+        assert_eq!(words.len(), 2048);
+        assert_eq!(words.bits_per_word(), 11.0)
+    }
+
+    #[test]
+    fn every_builtin_word_list_has_2048_words() {
+        for list in super::BuiltinList::ALL {
+            if let Ok(words) = super::WordList::builtin(*list) {
+                assert_eq!(words.len(), 2048, "{}", list.name())
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_word_list_file_panics() {
+        super::WordList::from_bytes(b"\n\n").unwrap();
+    }
+
     #[test]
     #[should_panic]
     fn empty_wordlist_panics() {
-        let words = super::WordList { inner: Vec::new() };
+        let words = super::WordList::new(Vec::<String>::new());
 
-        super::random_passphrase(&words, " ", 1);
+        super::random_passphrase(&mut rng(None, 0), &words, " ", 1);
     }
 }
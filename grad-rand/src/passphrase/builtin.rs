@@ -0,0 +1,57 @@
+macro_rules! builtin_lists {
+    ($($(#[$meta:meta])* $variant:ident => $name:literal, $feature:literal, $file:literal;)*) => {
+        /// The word lists which may be embedded in the program.
+        ///
+        /// Each list is only embedded if the cargo feature of the same name is enabled.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+        pub enum BuiltinList {
+            $($(#[$meta])* #[cfg_attr(feature = "clap", value(name = $name))] $variant),*
+        }
+
+        impl BuiltinList {
+            /// Every list, whether or not it is embedded.
+            pub const ALL: &[BuiltinList] = &[$(BuiltinList::$variant),*];
+
+            /// The name by which the list is selected.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(BuiltinList::$variant => $name),*
+                }
+            }
+
+            /// The compressed contents of the list, if it is embedded.
+            pub(super) fn data(self) -> Option<&'static [u8]> {
+                $(
+                    #[cfg(feature = $feature)]
+                    if self == BuiltinList::$variant { return Some(include_bytes!(concat!("../wordlist/", $file))) }
+                )*
+
+                None
+            }
+        }
+    };
+}
+
+builtin_lists! {
+    /// The BIP39 English word list.
+    Bip39English => "bip39-en", "bip39-en", "bip39-en.zst";
+    /// The BIP39 Spanish word list.
+    Bip39Spanish => "bip39-es", "bip39-es", "bip39-es.zst";
+    /// The BIP39 French word list.
+    Bip39French => "bip39-fr", "bip39-fr", "bip39-fr.zst";
+    /// The BIP39 Italian word list.
+    Bip39Italian => "bip39-it", "bip39-it", "bip39-it.zst";
+    /// The BIP39 Portuguese word list.
+    Bip39Portuguese => "bip39-pt", "bip39-pt", "bip39-pt.zst";
+    /// The BIP39 Czech word list.
+    Bip39Czech => "bip39-cs", "bip39-cs", "bip39-cs.zst";
+    /// The BIP39 Japanese word list.
+    Bip39Japanese => "bip39-ja", "bip39-ja", "bip39-ja.zst";
+    /// The BIP39 Korean word list.
+    Bip39Korean => "bip39-ko", "bip39-ko", "bip39-ko.zst";
+    /// The BIP39 Simplified Chinese word list.
+    Bip39ChineseSimplified => "bip39-zh-hans", "bip39-zh-hans", "bip39-zh-hans.zst";
+    /// The BIP39 Traditional Chinese word list.
+    Bip39ChineseTraditional => "bip39-zh-hant", "bip39-zh-hant", "bip39-zh-hant.zst";
+}
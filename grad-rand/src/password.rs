@@ -1,22 +1,258 @@
 use rand::prelude::*;
 use rand::distr::slice::Choose;
-use rand_chacha::ChaChaRng;
+use thiserror::Error;
 
-pub fn random_password(chars: &[char], length: usize) -> Vec<u8> {
+use crate::entropy::{self, EntropyError};
+use crate::rng::RandomSource;
+
+/// The greatest number of characters from a single class which a [`Policy`] may require.
+pub const MAX_MINIMUM: usize = 256;
+
+/// The greatest number of combinations of requirement counts which [`Policy::acceptances`] tracks,
+/// which bounds the time and memory needed to analyze a policy.
+pub const MAX_STATES: usize = 1 << 16;
+
+/// The smallest fraction of passwords which must satisfy a [`Policy`] for rejection sampling to be
+/// practical, which bounds the expected number of attempts per password to one million.
+const MIN_ACCEPTANCE: f64 = 1e-6;
+
+#[derive(Debug, Error)]
+pub enum PolicyError {
+    #[error("The password policy requires characters which are not in the alphabet.")]
+    Unsatisfiable,
+
+    #[error("A password of {0} characters cannot satisfy the password policy.")]
+    TooShort(usize),
+
+    #[error("Only {0:.2e} of passwords satisfy the password policy; use a longer password or relax the policy.")]
+    Improbable(f64),
+
+    #[error("A password policy may require at most {MAX_MINIMUM} characters from each class, not {0}.")]
+    MinimumTooLarge(usize),
+
+    #[error("The password policy has too many combinations of requirements to analyze; require fewer characters.")]
+    TooComplex,
+
+    #[error(transparent)]
+    Entropy(#[from] EntropyError)
+}
+
+/// A requirement that a password contain at least `minimum` characters from `chars`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    chars: Vec<char>,
+    minimum: usize
+}
+
+impl Requirement {
+    pub fn new(chars: impl Into<Vec<char>>, minimum: usize) -> Self {
+        Self { chars: chars.into(), minimum }
+    }
+
+    fn count(&self, password: &[char]) -> usize {
+        password.iter().filter(|char| self.chars.contains(char)).count()
+    }
+}
+
+/// A set of requirements which every password must satisfy.
+///
+/// Passwords are generated by rejection sampling: candidates are drawn uniformly from the alphabet
+/// and discarded until one satisfies the policy. The result is uniform over the passwords which
+/// satisfy the policy, whose entropy is reduced by `-log2` of the fraction of passwords accepted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Policy {
+    requirements: Vec<Requirement>
+}
+
+impl Policy {
+    pub fn new(requirements: Vec<Requirement>) -> Self {
+        Self { requirements }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requirements.iter().all(|requirement| requirement.minimum == 0)
+    }
+
+    pub fn is_satisfied_by(&self, password: &[char]) -> bool {
+        self.requirements.iter().all(|requirement| requirement.count(password) >= requirement.minimum)
+    }
+
+    /// Whether passwords of some length from `chars` satisfy the policy.
+    pub fn is_satisfiable(&self, chars: &[char]) -> bool {
+        self.requirements.iter()
+            .all(|requirement| requirement.minimum == 0 || requirement.chars.iter().any(|char| chars.contains(char)))
+    }
+
+    /// The fraction of passwords from `chars` which satisfy the policy, for each length in turn
+    /// starting from zero.
+    ///
+    /// Fails if the policy requires more than [`MAX_MINIMUM`] characters from a class, or has more
+    /// than [`MAX_STATES`] combinations of requirement counts to track.
+    pub fn acceptances(&self, chars: &[char]) -> Result<Acceptances<'_>, PolicyError> {
+        if let Some(requirement) = self.requirements.iter().find(|requirement| requirement.minimum > MAX_MINIMUM) {
+            return Err(PolicyError::MinimumTooLarge(requirement.minimum))
+        }
+
+        let strides: Vec<usize> = self.requirements.iter()
+            .scan(Some(1usize), |stride, requirement| {
+                let current = *stride;
+
+                *stride = stride.and_then(|stride| stride.checked_mul(requirement.minimum + 1));
+
+                Some(current)
+            })
+            .collect::<Option<_>>()
+            .ok_or(PolicyError::TooComplex)?;
+
+        let states = self.requirements.iter()
+            .try_fold(1usize, |states, requirement| states.checked_mul(requirement.minimum + 1))
+            .filter(|&states| states <= MAX_STATES)
+            .ok_or(PolicyError::TooComplex)?;
+
+        // Characters which count towards the same requirements are interchangeable, so group them.
+        let mut transitions: Vec<(Vec<usize>, f64)> = Vec::new();
+
+        for char in chars {
+            let members: Vec<usize> = self.requirements.iter()
+                .enumerate()
+                .filter(|(_, requirement)| requirement.chars.contains(char))
+                .map(|(index, _)| index)
+                .collect();
+
+            match transitions.iter_mut().find(|(existing, _)| *existing == members) {
+                Some((_, probability)) => *probability += 1.0 / chars.len() as f64,
+                None => transitions.push((members, 1.0 / chars.len() as f64))
+            }
+        }
+
+        let mut probabilities = vec![0.0; states];
+
+        probabilities[0] = 1.0;
+
+        Ok(Acceptances { requirements: &self.requirements, strides, transitions, probabilities })
+    }
+
+    /// The fraction of passwords of `length` characters from `chars` which satisfy the policy.
+    pub fn acceptance(&self, chars: &[char], length: usize) -> Result<f64, PolicyError> {
+        Ok(self.acceptances(chars)?.nth(length).unwrap_or_default())
+    }
+
+    /// The entropy of a password of `length` characters from `chars` which satisfies the policy, in bits.
+    pub fn entropy(&self, chars: &[char], length: usize) -> Result<f64, PolicyError> {
+        Ok(entropy::entropy(chars.len(), length) + self.acceptance(chars, length)?.log2())
+    }
+
+    /// The smallest length of at least `length` characters from `chars` at which a password which
+    /// satisfies the policy carries at least `bits` of entropy.
+    ///
+    /// Fails if more than [`entropy::MAX_LENGTH`] characters would be needed.
+    pub fn length_for_entropy(&self, chars: &[char], length: usize, bits: f64) -> Result<usize, PolicyError> {
+        if !self.is_satisfiable(chars) { return Err(PolicyError::Unsatisfiable) }
+
+        let mut acceptances = self.acceptances(chars)?.enumerate().skip(length);
+
+        acceptances
+            .find(|&(length, acceptance)| {
+                length > entropy::MAX_LENGTH || entropy::entropy(chars.len(), length) + acceptance.log2() >= bits
+            })
+            .map(|(length, _)| length)
+            .filter(|&length| length <= entropy::MAX_LENGTH)
+            .ok_or(PolicyError::Entropy(EntropyError::TooLong { bits }))
+    }
+
+    /// Check that passwords of `length` characters from `chars` can practically satisfy the policy,
+    /// returning the fraction of passwords which do.
+    pub fn check(&self, chars: &[char], length: usize) -> Result<f64, PolicyError> {
+        if !self.is_satisfiable(chars) { return Err(PolicyError::Unsatisfiable) }
+
+        match self.acceptance(chars, length)? {
+            0.0 => Err(PolicyError::TooShort(length)),
+            acceptance if acceptance < MIN_ACCEPTANCE => Err(PolicyError::Improbable(acceptance)),
+            acceptance => Ok(acceptance)
+        }
+    }
+}
+
+/// The fraction of passwords which satisfy a [`Policy`], for each length in turn, returned by
+/// [`Policy::acceptances`].
+///
+/// This is computed exactly by tracking, one character at a time, the probability of each
+/// combination of requirement counts, capped at their minimums.
+#[derive(Debug, Clone)]
+pub struct Acceptances<'a> {
+    requirements: &'a [Requirement],
+    strides: Vec<usize>,
+    transitions: Vec<(Vec<usize>, f64)>,
+    probabilities: Vec<f64>
+}
+
+impl Iterator for Acceptances<'_> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        let acceptance = self.probabilities[self.probabilities.len() - 1];
+
+        // An empty alphabet only produces the empty password, whose acceptance never changes.
+        if self.transitions.is_empty() { return Some(acceptance) }
+
+        let mut next = vec![0.0; self.probabilities.len()];
+
+        for (state, &probability) in self.probabilities.iter().enumerate() {
+            if probability == 0.0 { continue }
+
+            for (members, weight) in &self.transitions {
+                let target = members.iter().fold(state, |target, &index| {
+                    let minimum = self.requirements[index].minimum;
+                    let count = state / self.strides[index] % (minimum + 1);
+
+                    if count < minimum { target + self.strides[index] } else { target }
+                });
+
+                next[target] += probability * weight;
+            }
+        }
+
+        self.probabilities = next;
+
+        Some(acceptance)
+    }
+}
+
+pub fn random_password<R: RandomSource + ?Sized>(rng: &mut R, chars: &[char], length: usize) -> Vec<u8> {
     if length == 0 { return Vec::new() }
 
-    let rng = ChaChaRng::from_rng(&mut rand::rng());
     let distribution = Choose::new(chars).unwrap();
 
     rng.sample_iter(distribution).take(length).collect::<String>().into_bytes()
 }
 
+/// Generate a password which satisfies `policy` by rejection sampling.
+///
+/// Call [`Policy::check`] first: a policy which no password satisfies never terminates.
+pub fn random_password_with_policy<R: RandomSource + ?Sized>(rng: &mut R, chars: &[char], policy: &Policy, length: usize) -> Vec<u8> {
+    if length == 0 { return Vec::new() }
+
+    let distribution = Choose::new(chars).unwrap();
+    let mut password = Vec::with_capacity(length);
+
+    loop {
+        password.clear();
+        password.extend(rng.sample_iter(&distribution).take(length));
+
+        if policy.is_satisfied_by(&password) { return password.into_iter().collect::<String>().into_bytes() }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::rng::rng;
+
+    use super::{Policy, PolicyError, Requirement};
+
     #[test]
     fn can_generate_empty_password() {
         let chars: Vec<char> = "!@*-_.0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().collect();
-        let bytes = super::random_password(&chars, 0);
+        let bytes = super::random_password(&mut rng(None, 0), &chars, 0);
 
         assert_eq!(bytes.len(), 0)
     }
@@ -24,9 +260,90 @@ mod tests {
     #[test]
     fn can_generate_ten_thousand_character_password() {
         let chars: Vec<char> = "!@*-_.0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().collect();
-        let bytes = super::random_password(&chars, 10000);
+        let bytes = super::random_password(&mut rng(None, 0), &chars, 10000);
         let string = std::str::from_utf8(&bytes).unwrap();
 
         assert_eq!(string.chars().count(), 10000)
     }
+
+    #[test]
+    fn computes_acceptance_exactly() {
+        let chars: Vec<char> = "ab1".chars().collect();
+        let policy = Policy::new(vec![Requirement::new(['1'], 1)]);
+
+        // One minus the probability that none of the three characters is a digit.
+        assert!((policy.acceptance(&chars, 3).unwrap() - 19.0 / 27.0).abs() < 1e-12);
+        assert_eq!(Policy::default().acceptance(&chars, 3).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn computes_acceptance_of_several_requirements() {
+        let chars: Vec<char> = "ab12".chars().collect();
+        let policy = Policy::new(vec![Requirement::new(['a', 'b'], 2), Requirement::new(['1', '2'], 1)]);
+
+        // Of the 16 equally likely class patterns of four characters, those with at least two letters
+        // and at least one digit are the 4 with three letters and the 6 with two.
+        assert!((policy.acceptance(&chars, 4).unwrap() - 10.0 / 16.0).abs() < 1e-12);
+        assert_eq!(policy.acceptance(&chars, 2).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn policy_reduces_entropy() {
+        let chars: Vec<char> = "ab1".chars().collect();
+        let policy = Policy::new(vec![Requirement::new(['1'], 1)]);
+
+        assert!((policy.entropy(&chars, 3).unwrap() - 19f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn finds_length_for_entropy() {
+        let chars: Vec<char> = "ab1".chars().collect();
+        let policy = Policy::new(vec![Requirement::new(['1'], 1)]);
+        let length = policy.length_for_entropy(&chars, 0, 32.0).unwrap();
+
+        assert!(policy.entropy(&chars, length).unwrap() >= 32.0);
+        assert!(policy.entropy(&chars, length - 1).unwrap() < 32.0);
+        assert_eq!(policy.length_for_entropy(&chars, 40, 32.0).unwrap(), 40);
+        assert!(matches!(policy.length_for_entropy(&chars, 0, 1e300), Err(PolicyError::Entropy(..))));
+    }
+
+    #[test]
+    fn rejects_policies_too_large_to_analyze() {
+        let chars: Vec<char> = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().collect();
+        let digits: Vec<char> = "0123456789".chars().collect();
+        let lower: Vec<char> = "abcdefghijklmnopqrstuvwxyz".chars().collect();
+        let upper: Vec<char> = "ABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().collect();
+
+        let policy = Policy::new(vec![Requirement::new(digits.clone(), 1000)]);
+
+        assert!(matches!(policy.check(&chars, 1000), Err(PolicyError::MinimumTooLarge(1000))));
+
+        let policy = Policy::new(vec![Requirement::new(digits, 200), Requirement::new(lower, 200), Requirement::new(upper, 200)]);
+
+        assert!(matches!(policy.check(&chars, 1000), Err(PolicyError::TooComplex)));
+    }
+
+    #[test]
+    fn generates_passwords_which_satisfy_policy() {
+        let chars: Vec<char> = "0123456789abcdefghijklmnopqrstuvwxyz!@".chars().collect();
+        let policy = Policy::new(vec![Requirement::new(['!', '@'], 2), Requirement::new("0123456789".chars().collect::<Vec<_>>(), 3)]);
+        let mut rng = rng(None, 0);
+
+        for _ in 0..100 {
+            let bytes = super::random_password_with_policy(&mut rng, &chars, &policy, 8);
+            let password: Vec<char> = String::from_utf8(bytes).unwrap().chars().collect();
+
+            assert_eq!(password.len(), 8);
+            assert!(policy.is_satisfied_by(&password));
+        }
+    }
+
+    #[test]
+    fn rejects_impossible_policies() {
+        let chars: Vec<char> = "abc".chars().collect();
+
+        assert!(matches!(Policy::new(vec![Requirement::new(['1'], 1)]).check(&chars, 8), Err(PolicyError::Unsatisfiable)));
+        assert!(matches!(Policy::new(vec![Requirement::new(['a'], 4)]).check(&chars, 3), Err(PolicyError::TooShort(3))));
+        assert!(matches!(Policy::new(vec![Requirement::new(['a'], 30)]).check(&chars, 30), Err(PolicyError::Improbable(..))));
+    }
 }
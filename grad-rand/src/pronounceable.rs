@@ -0,0 +1,122 @@
+//! Passwords built from consonant-vowel syllables, which are easy to read aloud.
+//!
+//! Because each position is drawn from a small alphabet chosen by its slot, these passwords carry
+//! considerably less entropy than uniform passwords of the same length. Use [`template_entropy`]
+//! rather than the length to judge their strength.
+
+use std::sync::LazyLock;
+
+use rand::prelude::*;
+use rand::distr::slice::Choose;
+
+use crate::entropy::{self, EntropyError, MAX_LENGTH};
+use crate::rng::RandomSource;
+
+/// Consonants which are hard to mishear when spoken, omitting `c`, `q`, `w`, `x` and `y`.
+const CONSONANTS: &[char; 16] = &['b', 'd', 'f', 'g', 'h', 'j', 'k', 'l', 'm', 'n', 'p', 'r', 's', 't', 'v', 'z'];
+
+const VOWELS: &[char; 5] = &['a', 'e', 'i', 'o', 'u'];
+
+const DIGITS: &[char; 10] = &['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+const SYMBOLS: &[char; 6] = &['!', '@', '*', '-', '_', '.'];
+
+static DISTRS: LazyLock<[Choose<'static, char>; 4]> = LazyLock::new(|| [
+    Choose::new(CONSONANTS).unwrap(),
+    Choose::new(VOWELS).unwrap(),
+    Choose::new(DIGITS).unwrap(),
+    Choose::new(SYMBOLS).unwrap()
+]);
+
+/// A single position in a template, which determines the alphabet from which its character is drawn.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Slot {
+    Consonant,
+    Vowel,
+    Digit,
+    Symbol
+}
+
+impl Slot {
+    /// The characters from which the character in this slot is drawn.
+    pub fn chars(self) -> &'static [char] {
+        match self {
+            Slot::Consonant => CONSONANTS,
+            Slot::Vowel => VOWELS,
+            Slot::Digit => DIGITS,
+            Slot::Symbol => SYMBOLS
+        }
+    }
+
+    fn distribution(self) -> &'static Choose<'static, char> {
+        &DISTRS[self as usize]
+    }
+}
+
+/// The entropy of a password generated from `template`, in bits.
+pub fn template_entropy(template: &[Slot]) -> f64 {
+    template.iter().map(|slot| entropy::bits_per_symbol(slot.chars().len())).sum()
+}
+
+/// The number of times `template` must be repeated for a password to carry at least `bits` of
+/// entropy, which is never less than one.
+///
+/// Fails if more than [`MAX_LENGTH`] characters would be needed.
+pub fn repeats_for_entropy(template: &[Slot], bits: f64) -> Result<usize, EntropyError> {
+    if !bits.is_finite() { return Err(EntropyError::NotFinite(bits)) }
+
+    let entropy = template_entropy(template);
+
+    if bits <= entropy { return Ok(1) }
+    if entropy == 0.0 { return Err(EntropyError::Alphabet { alphabet: 0, bits }) }
+
+    let repeats = (bits / entropy).ceil();
+
+    if repeats * template.len() as f64 > MAX_LENGTH as f64 { return Err(EntropyError::TooLong { bits }) }
+
+    Ok(repeats as usize)
+}
+
+/// Generate a password with one character drawn uniformly from the alphabet of each slot of `template`.
+pub fn random_pronounceable<R: RandomSource + ?Sized>(rng: &mut R, template: &[Slot]) -> Vec<u8> {
+    template.iter()
+        .map(|slot| rng.sample(slot.distribution()))
+        .collect::<String>()
+        .into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::rng;
+
+    use super::Slot::{Consonant, Digit, Symbol, Vowel};
+
+    #[test]
+    fn follows_template() {
+        let template = [Consonant, Vowel, Consonant, Consonant, Vowel, Consonant, Digit, Symbol];
+        let bytes = super::random_pronounceable(&mut rng(None, 0), &template);
+        let password: Vec<char> = String::from_utf8(bytes).unwrap().chars().collect();
+
+        assert_eq!(password.len(), template.len());
+        assert!(template.iter().zip(&password).all(|(slot, c)| slot.chars().contains(c)));
+    }
+
+    #[test]
+    fn computes_template_entropy() {
+        // Sixteen consonants carry four bits each and five vowels log2(5) bits each.
+        let entropy = super::template_entropy(&[Consonant, Vowel, Consonant, Consonant, Vowel, Consonant]);
+
+        assert!((entropy - (16.0 + 2.0 * 5f64.log2())).abs() < 1e-9);
+        assert_eq!(super::template_entropy(&[]), 0.0);
+    }
+
+    #[test]
+    fn computes_repeats_for_entropy() {
+        let template = [Consonant, Vowel, Consonant];
+
+        assert_eq!(super::repeats_for_entropy(&template, 1.0).unwrap(), 1);
+        assert_eq!(super::repeats_for_entropy(&template, 64.0).unwrap(), 7);
+        assert!(super::repeats_for_entropy(&template, 1e300).is_err());
+        assert!(super::repeats_for_entropy(&[], 8.0).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Statistical tests of the uniformity of the generators, which would catch a regression such as
+//! modulo bias.
+
+use grad_analyze::randomness::{self, Outcome};
+
+use crate::byte::random_byte;
+use crate::digit::random_digit;
+use crate::password::random_password;
+use crate::rng::rng;
+
+/// A lower significance level than the conventional 0.01, so that a change of seed is unlikely to
+/// fail a test by chance.
+const SIGNIFICANCE: f64 = 0.001;
+
+const SEED: Option<[u8; 32]> = Some([42; 32]);
+
+/// Convert symbols drawn uniformly from an alphabet of `size` into bits which are uniform if the
+/// symbols are.
+///
+/// A symbol below the largest power of two not exceeding `size` contributes its bits. Any other
+/// symbol is uniform among the remaining symbols, which are treated as a smaller alphabet in turn.
+fn symbol_bits(symbols: impl IntoIterator<Item = usize>, size: usize) -> Vec<bool> {
+    let mut bits = Vec::new();
+
+    for symbol in symbols {
+        let (mut symbol, mut size) = (symbol, size);
+
+        while size > 1 {
+            let width = size.ilog2();
+
+            if symbol < 1 << width {
+                bits.extend((0..width).rev().map(|shift| symbol >> shift & 1 == 1));
+                break
+            }
+
+            symbol -= 1 << width;
+            size -= 1 << width;
+        }
+    }
+
+    bits
+}
+
+fn failures(bits: &[bool]) -> Vec<Outcome> {
+    randomness::run(bits).unwrap().into_iter().filter(|outcome| !outcome.passed(SIGNIFICANCE)).collect()
+}
+
+fn printable() -> Vec<char> {
+    ('!'..='~').collect()
+}
+
+#[test]
+fn random_bytes_are_uniform() {
+    let bytes = random_byte(&mut rng(SEED, 0), 128 * 1024);
+
+    assert_eq!(failures(&randomness::bits(&bytes)), Vec::new());
+}
+
+#[test]
+fn random_digits_are_uniform() {
+    let digits = random_digit(&mut rng(SEED, 0), 1_000_000);
+    let bits = symbol_bits(digits.iter().map(|digit| usize::from(digit - b'0')), 10);
+
+    assert_eq!(failures(&bits), Vec::new());
+}
+
+#[test]
+fn random_passwords_are_uniform() {
+    let alphabet = printable();
+    let password = random_password(&mut rng(SEED, 0), &alphabet, 500_000);
+    let bits = symbol_bits(password.iter().map(|&char| alphabet.iter().position(|&other| other == char as char).unwrap()), alphabet.len());
+
+    assert_eq!(failures(&bits), Vec::new());
+}
+
+#[test]
+fn detects_modulo_bias() {
+    // Reducing each byte modulo the size of the alphabet favours its first symbols.
+    let bytes = random_byte(&mut rng(SEED, 0), 1_000_000);
+
+    assert!(!failures(&symbol_bits(bytes.iter().map(|&byte| usize::from(byte) % 10), 10)).is_empty());
+    assert!(!failures(&symbol_bits(bytes.iter().map(|&byte| usize::from(byte) % 94), 94)).is_empty());
+}
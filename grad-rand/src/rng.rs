@@ -0,0 +1,321 @@
+use std::io::{self, ErrorKind, Read};
+use std::sync::{Arc, Mutex};
+
+use rand::prelude::*;
+use rand::rand_core::impls;
+use rand::rngs::OsRng;
+use rand::TryRngCore;
+use rand_chacha::{ChaCha8Rng, ChaCha12Rng, ChaCha20Rng, ChaChaRng};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SourceError {
+    #[error("The {0} random source cannot be seeded.")]
+    Unseedable(&'static str),
+
+    #[error("The random stream ran out of bytes.")]
+    Exhausted,
+
+    #[error("Failed to read from the random stream: {0}")]
+    Stream(#[source] io::Error)
+}
+
+/// A source of random bytes from which every generator draws its output.
+pub trait RandomSource: RngCore + Send {
+    /// The name of the algorithm which produces the output of this source.
+    fn name(&self) -> &'static str;
+
+    /// Position the source at the start of the stream numbered `stream`.
+    ///
+    /// Sources which cannot be positioned ignore this call.
+    fn seek(&mut self, stream: u64) {
+        let _ = stream;
+    }
+
+    /// Report the error which stopped the source since the last check, if any.
+    ///
+    /// Output drawn from a source after it fails is meaningless, so callers must check the source
+    /// before using its output. Sources which cannot fail always succeed.
+    fn check(&mut self) -> Result<(), SourceError> {
+        Ok(())
+    }
+}
+
+impl<S: RandomSource + ?Sized> RandomSource for Box<S> {
+    fn name(&self) -> &'static str { (**self).name() }
+
+    fn seek(&mut self, stream: u64) { (**self).seek(stream) }
+
+    fn check(&mut self) -> Result<(), SourceError> { (**self).check() }
+}
+
+macro_rules! chacha_source_impl {
+    ($($type:ident => $name:literal),*) => {
+        $(
+            impl RandomSource for $type {
+                fn name(&self) -> &'static str { $name }
+
+                fn seek(&mut self, stream: u64) {
+                    self.set_stream(stream);
+                    self.set_word_pos(0);
+                }
+            }
+        )*
+    };
+}
+
+chacha_source_impl!(ChaCha8Rng => "ChaCha8", ChaCha12Rng => "ChaCha12", ChaCha20Rng => "ChaCha20");
+
+/// Draws bytes directly from the operating system's entropy source.
+///
+/// # Panics
+///
+/// Panics if the operating system fails to provide entropy.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OsSource;
+
+impl RngCore for OsSource {
+    fn next_u32(&mut self) -> u32 {
+        OsRng.try_next_u32().unwrap()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        OsRng.try_next_u64().unwrap()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        OsRng.try_fill_bytes(dest).unwrap()
+    }
+}
+
+impl RandomSource for OsSource {
+    fn name(&self) -> &'static str { "OS" }
+}
+
+/// Draws bytes verbatim from a user-supplied stream, such as a file of pre-recorded output.
+///
+/// Once the stream is exhausted or cannot be read, the source records the error, which is reported
+/// by [`RandomSource::check`], and draws further bytes from the thread-local generator so that
+/// callers which retry until they find an acceptable value still terminate.
+#[derive(Debug)]
+pub struct StreamSource<R> {
+    reader: R,
+    error: Option<SourceError>
+}
+
+impl<R: Read> StreamSource<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader, error: None }
+    }
+
+    /// Fill `dest` with the next bytes of the stream.
+    pub fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), SourceError> {
+        self.reader.read_exact(dest).map_err(|error| match error.kind() {
+            ErrorKind::UnexpectedEof => SourceError::Exhausted,
+            _ => SourceError::Stream(error)
+        })
+    }
+}
+
+impl<R: Read> RngCore for StreamSource<R> {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if self.error.is_none() && let Err(error) = self.try_fill_bytes(dest) {
+            self.error = Some(error);
+        }
+
+        if self.error.is_some() { rand::rng().fill_bytes(dest) }
+    }
+}
+
+impl<R: Read + Send> RandomSource for StreamSource<R> {
+    fn name(&self) -> &'static str { "Stream" }
+
+    fn check(&mut self) -> Result<(), SourceError> {
+        self.error.take().map_or(Ok(()), Err)
+    }
+}
+
+/// A handle to a random source which may be shared between threads.
+///
+/// Every call locks the underlying source, so the order in which concurrent callers receive
+/// bytes is unspecified.
+#[derive(Clone)]
+pub struct SharedSource(Arc<Mutex<dyn RandomSource>>);
+
+impl SharedSource {
+    pub fn new<S: RandomSource + 'static>(source: S) -> Self {
+        Self(Arc::new(Mutex::new(source)))
+    }
+}
+
+impl RngCore for SharedSource {
+    fn next_u32(&mut self) -> u32 {
+        self.0.lock().unwrap().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.lock().unwrap().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.lock().unwrap().fill_bytes(dest)
+    }
+}
+
+impl RandomSource for SharedSource {
+    fn name(&self) -> &'static str {
+        self.0.lock().unwrap().name()
+    }
+
+    fn check(&mut self) -> Result<(), SourceError> {
+        self.0.lock().unwrap().check()
+    }
+}
+
+/// The algorithms which may be used to produce random output.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Backend {
+    /// The ChaCha stream cipher with 8 rounds.
+    #[cfg_attr(feature = "clap", value(name = "chacha8"))]
+    ChaCha8,
+
+    /// The ChaCha stream cipher with 12 rounds.
+    #[cfg_attr(feature = "clap", value(name = "chacha12"))]
+    ChaCha12,
+
+    /// The ChaCha stream cipher with 20 rounds.
+    #[default]
+    #[cfg_attr(feature = "clap", value(name = "chacha20"))]
+    ChaCha20,
+
+    /// The operating system's entropy source. Cannot be seeded.
+    Os
+}
+
+macro_rules! chacha {
+    ($type:ty, $seed:expr, $index:expr) => {
+        match $seed {
+            Some(seed) => {
+                let mut rng = <$type>::from_seed(seed);
+
+                rng.set_stream($index);
+
+                Box::new(rng)
+            }
+            None => Box::new(<$type>::from_rng(&mut rand::rng()))
+        }
+    };
+}
+
+impl Backend {
+    /// Create the random source used to produce the item at `index` of a batch.
+    ///
+    /// Seeded sources select the ChaCha stream numbered `index`, so each item is reproducible
+    /// regardless of the order in which a batch is generated.
+    pub fn source(self, seed: Option<[u8; 32]>, index: u64) -> Result<Box<dyn RandomSource>, SourceError> {
+        let source: Box<dyn RandomSource> = match self {
+            Backend::ChaCha8 => chacha!(ChaCha8Rng, seed, index),
+            Backend::ChaCha12 => chacha!(ChaCha12Rng, seed, index),
+            Backend::ChaCha20 => chacha!(ChaCha20Rng, seed, index),
+            Backend::Os if seed.is_some() => return Err(SourceError::Unseedable(OsSource.name())),
+            Backend::Os => Box::new(OsSource)
+        };
+
+        Ok(source)
+    }
+}
+
+/// Create a random source using the default backend.
+///
+/// See [`Backend::source`] for the meaning of `seed` and `index`.
+pub fn rng(seed: Option<[u8; 32]>, index: u64) -> ChaChaRng {
+    match seed {
+        Some(seed) => {
+            let mut rng = ChaChaRng::from_seed(seed);
+
+            rng.set_stream(index);
+
+            rng
+        }
+        None => ChaChaRng::from_rng(&mut rand::rng())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::RngCore;
+
+    use super::{Backend, RandomSource, SourceError, StreamSource};
+
+    #[test]
+    fn seeded_streams_are_reproducible() {
+        let seed = Some([7u8; 32]);
+
+        assert_eq!(super::rng(seed, 3).next_u64(), super::rng(seed, 3).next_u64());
+        assert_ne!(super::rng(seed, 3).next_u64(), super::rng(seed, 4).next_u64());
+    }
+
+    #[test]
+    fn default_backend_matches_rng() {
+        let seed = Some([7u8; 32]);
+
+        assert_eq!(Backend::default().source(seed, 3).unwrap().next_u64(), super::rng(seed, 3).next_u64());
+    }
+
+    #[test]
+    fn seeking_restarts_stream() {
+        let seed = Some([7u8; 32]);
+        let mut source = Backend::ChaCha8.source(seed, 0).unwrap();
+
+        source.next_u64();
+        source.seek(5);
+
+        assert_eq!(source.next_u64(), Backend::ChaCha8.source(seed, 5).unwrap().next_u64());
+    }
+
+    #[test]
+    fn seeded_backends_differ() {
+        let seed = Some([7u8; 32]);
+        let chacha8 = Backend::ChaCha8.source(seed, 0).unwrap().next_u64();
+        let chacha20 = Backend::ChaCha20.source(seed, 0).unwrap().next_u64();
+
+        assert_ne!(chacha8, chacha20);
+    }
+
+    #[test]
+    fn stream_source_yields_stream_verbatim() {
+        let mut source = StreamSource::new(&[1u8, 2, 3, 4][..]);
+        let mut buffer = [0u8; 4];
+
+        source.fill_bytes(&mut buffer);
+
+        assert_eq!(buffer, [1, 2, 3, 4]);
+        assert!(source.check().is_ok());
+    }
+
+    #[test]
+    fn exhausted_stream_source_reports_error() {
+        let mut source = StreamSource::new(&[1u8, 2, 3][..]);
+        let mut buffer = [0u8; 4];
+
+        source.fill_bytes(&mut buffer);
+
+        assert!(matches!(source.check(), Err(SourceError::Exhausted)));
+        assert!(matches!(source.try_fill_bytes(&mut buffer), Err(SourceError::Exhausted)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn seeding_os_backend_panics() {
+        Backend::Os.source(Some([0u8; 32]), 0).unwrap();
+    }
+}
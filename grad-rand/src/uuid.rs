@@ -0,0 +1,146 @@
+//! Universally unique identifiers, as defined by RFC 9562.
+
+use rand::prelude::*;
+use uuid::{Builder, Uuid};
+
+use crate::rng::RandomSource;
+
+/// The number of bits of a version 7 UUID devoted to its counter, which occupies the 12 bits of
+/// `rand_a` and the 30 most significant bits of `rand_b`.
+pub const COUNTER_BITS: u32 = 42;
+
+/// The textual forms of a UUID.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum UuidFormat {
+    /// `67e55044-10b1-426f-9247-bb680e5fe0c8`
+    #[default]
+    Hyphenated,
+
+    /// `67e5504410b1426f9247bb680e5fe0c8`
+    Simple,
+
+    /// `urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8`
+    Urn,
+
+    /// `{67e55044-10b1-426f-9247-bb680e5fe0c8}`
+    Braced
+}
+
+/// Write `uuid` in the given form.
+pub fn format_uuid(uuid: Uuid, format: UuidFormat, uppercase: bool) -> Vec<u8> {
+    let mut buffer = [0u8; uuid::fmt::Urn::LENGTH];
+
+    let string = match (format, uppercase) {
+        (UuidFormat::Hyphenated, false) => uuid.hyphenated().encode_lower(&mut buffer),
+        (UuidFormat::Hyphenated, true) => uuid.hyphenated().encode_upper(&mut buffer),
+        (UuidFormat::Simple, false) => uuid.simple().encode_lower(&mut buffer),
+        (UuidFormat::Simple, true) => uuid.simple().encode_upper(&mut buffer),
+        (UuidFormat::Urn, false) => uuid.urn().encode_lower(&mut buffer),
+        (UuidFormat::Urn, true) => uuid.urn().encode_upper(&mut buffer),
+        (UuidFormat::Braced, false) => uuid.braced().encode_lower(&mut buffer),
+        (UuidFormat::Braced, true) => uuid.braced().encode_upper(&mut buffer)
+    };
+
+    string.as_bytes().to_vec()
+}
+
+/// Generate a random (version 4) UUID.
+pub fn random_uuid<R: RandomSource + ?Sized>(rng: &mut R) -> Uuid {
+    let mut bytes = [0u8; 16];
+
+    rng.fill_bytes(&mut bytes);
+
+    Builder::from_random_bytes(bytes).into_uuid()
+}
+
+/// Generate a time-ordered (version 7) UUID from a Unix timestamp in milliseconds and a counter.
+///
+/// The counter fills the first [`COUNTER_BITS`] bits after the timestamp, so UUIDs which share a
+/// timestamp sort in the order of their counters. The remaining 32 bits are random.
+pub fn time_uuid<R: RandomSource + ?Sized>(rng: &mut R, timestamp: u64, counter: u64) -> Uuid {
+    let mut bytes = [0u8; 16];
+
+    bytes[..6].copy_from_slice(&timestamp.to_be_bytes()[2..]);
+    bytes[6] = 0x70 | ((counter >> 38) & 0x0f) as u8;
+    bytes[7] = (counter >> 30) as u8;
+    bytes[8] = 0x80 | ((counter >> 24) & 0x3f) as u8;
+    bytes[9] = (counter >> 16) as u8;
+    bytes[10] = (counter >> 8) as u8;
+    bytes[11] = counter as u8;
+
+    rng.fill_bytes(&mut bytes[12..]);
+
+    Uuid::from_bytes(bytes)
+}
+
+/// Choose the initial counter of a batch of version 7 UUIDs at random.
+///
+/// The most significant bit of the counter is left clear, as RFC 9562 recommends, so that it cannot
+/// overflow into the timestamp within any practical batch.
+pub fn initial_counter<R: RandomSource + ?Sized>(rng: &mut R) -> u64 {
+    rng.random::<u64>() >> (64 - COUNTER_BITS + 1)
+}
+
+/// Generate a name-based (version 5) UUID from the SHA-1 hash of a namespace and a name.
+pub fn name_uuid(namespace: [u8; 16], name: &[u8]) -> Uuid {
+    Uuid::new_v5(&Uuid::from_bytes(namespace), name)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::{Variant, Version};
+
+    use crate::rng::rng;
+
+    use super::UuidFormat;
+
+    #[test]
+    fn generates_random_uuid() {
+        let uuid = super::random_uuid(&mut rng(None, 0));
+
+        assert_eq!(uuid.get_version(), Some(Version::Random));
+        assert_eq!(uuid.get_variant(), Variant::RFC4122);
+    }
+
+    #[test]
+    fn time_uuids_are_monotonic() {
+        let mut rng = rng(None, 0);
+        let counter = super::initial_counter(&mut rng);
+
+        let uuids: Vec<_> = (0..1000)
+            .map(|index| super::time_uuid(&mut rng, 1_700_000_000_000, counter + index))
+            .collect();
+
+        assert!(uuids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(uuids[0].get_version(), Some(Version::SortRand));
+        assert_eq!(uuids[0].get_variant(), Variant::RFC4122);
+        assert_eq!(uuids[0].get_timestamp().unwrap().to_unix(), (1_700_000_000, 0));
+    }
+
+    #[test]
+    fn counter_spans_rand_a_and_rand_b() {
+        let uuid = super::time_uuid(&mut rng(None, 0), 0, (1 << super::COUNTER_BITS) - 1);
+        let bytes = uuid.as_bytes();
+
+        assert_eq!(&bytes[6..12], &[0x7f, 0xff, 0xbf, 0xff, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn generates_name_uuid() {
+        let dns = *uuid::Uuid::NAMESPACE_DNS.as_bytes();
+
+        // The example given in appendix A.4 of RFC 9562.
+        assert_eq!(super::name_uuid(dns, b"www.example.com").to_string(), "2ed6657d-e927-568b-95e1-2665a8aea6a2");
+    }
+
+    #[test]
+    fn formats_uuid() {
+        let uuid = uuid::Uuid::NAMESPACE_DNS;
+
+        assert_eq!(super::format_uuid(uuid, UuidFormat::Hyphenated, false), b"6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+        assert_eq!(super::format_uuid(uuid, UuidFormat::Simple, true), b"6BA7B8109DAD11D180B400C04FD430C8");
+        assert_eq!(super::format_uuid(uuid, UuidFormat::Urn, false), b"urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+        assert_eq!(super::format_uuid(uuid, UuidFormat::Braced, false), b"{6ba7b810-9dad-11d1-80b4-00c04fd430c8}");
+    }
+}
@@ -0,0 +1,416 @@
+//! Durations, and arithmetic on timestamps with them.
+
+use std::fmt;
+use std::ops::Neg;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Days, FixedOffset, Months, NaiveDateTime, TimeDelta, TimeZone};
+use regex::{Captures, Regex};
+use thiserror::Error;
+
+use crate::zone::{Zone, ZoneError};
+
+static ISO8601: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"^(?<sign>[+-])?P(?:(?<years>[0-9]+)Y)?(?:(?<months>[0-9]+)M)?(?:(?<weeks>[0-9]+)W)?(?:(?<days>[0-9]+)D)?",
+        r"(?:T(?:(?<hours>[0-9]+(?:[.,][0-9]+)?)H)?(?:(?<minutes>[0-9]+(?:[.,][0-9]+)?)M)?(?:(?<seconds>[0-9]+(?:[.,][0-9]+)?)S)?)?$"
+    ))
+    .unwrap()
+});
+
+static HUMAN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?<sign>[+-])?\s*(?:[0-9]+(?:\.[0-9]+)?\s*(?:ns|us|µs|ms|mo|y|w|d|h|m|s)\s*)+$").unwrap()
+});
+
+static HUMAN_TERM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?<value>[0-9]+)(?:\.(?<fraction>[0-9]+))?\s*(?<unit>ns|us|µs|ms|mo|y|w|d|h|m|s)").unwrap()
+});
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+#[derive(Debug, Error)]
+pub enum DurationError {
+    #[error("'{0}' is not an ISO 8601 duration such as P1DT2H, or a duration such as 90m or 2w3d.")]
+    Invalid(String),
+
+    #[error("Only hours, minutes and smaller units may have a fraction, as years, months, weeks and days vary in length.")]
+    Fraction,
+
+    #[error("The duration or the timestamp is out of range.")]
+    Overflow,
+
+    #[error(transparent)]
+    Zone(#[from] ZoneError)
+}
+
+/// A duration of calendar months and days, which vary in length, and of an exact amount of time.
+///
+/// Years are twelve months and weeks are seven days. Adding a month keeps the day of the month,
+/// unless the month is shorter, and adding a day keeps the time of day, even across a change of
+/// offset.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Duration {
+    pub months: i64,
+    pub days: i64,
+    pub time: TimeDelta
+}
+
+impl Duration {
+    fn from_nanos(months: i64, days: i64, nanos: i128) -> Result<Duration, DurationError> {
+        let seconds = i64::try_from(nanos.div_euclid(NANOS_PER_SECOND)).map_err(|_| DurationError::Overflow)?;
+        let time = TimeDelta::new(seconds, nanos.rem_euclid(NANOS_PER_SECOND) as u32).ok_or(DurationError::Overflow)?;
+
+        Ok(Duration { months, days, time })
+    }
+
+    fn parse_iso8601(captures: &Captures) -> Result<Duration, DurationError> {
+        let integer = |name: &str| captures.name(name).map_or(Ok(0), |value| value.as_str().parse::<i64>().map_err(|_| DurationError::Overflow));
+        let decimal = |name: &str, unit: i128| captures.name(name).map_or(Ok(0), |value| nanos(value.as_str(), unit));
+
+        let months = integer("years")?.checked_mul(12).and_then(|years| years.checked_add(integer("months").ok()?));
+        let days = integer("weeks")?.checked_mul(7).and_then(|weeks| weeks.checked_add(integer("days").ok()?));
+        let time = decimal("hours", 3600)?
+            .checked_add(decimal("minutes", 60)?)
+            .and_then(|time| time.checked_add(decimal("seconds", 1).ok()?));
+
+        match (months, days, time) {
+            (Some(months), Some(days), Some(time)) => Duration::from_nanos(months, days, time),
+            _ => Err(DurationError::Overflow)
+        }
+    }
+
+    fn parse_human(input: &str) -> Result<Duration, DurationError> {
+        let (mut months, mut days, mut time) = (0i64, 0i64, 0i128);
+
+        for captures in HUMAN_TERM.captures_iter(input) {
+            let value = match captures.name("fraction") {
+                Some(fraction) => format!("{}.{}", &captures["value"], fraction.as_str()),
+                None => String::from(&captures["value"])
+            };
+
+            let whole = || match captures.name("fraction") {
+                Some(_) => Err(DurationError::Fraction),
+                None => captures["value"].parse::<i64>().map_err(|_| DurationError::Overflow)
+            };
+
+            let (calendar, count) = match &captures["unit"] {
+                "y" => (&mut months, whole()?.checked_mul(12)),
+                "mo" => (&mut months, Some(whole()?)),
+                "w" => (&mut days, whole()?.checked_mul(7)),
+                "d" => (&mut days, Some(whole()?)),
+                unit => {
+                    let scale = match unit {
+                        "h" => 3600 * NANOS_PER_SECOND,
+                        "m" => 60 * NANOS_PER_SECOND,
+                        "s" => NANOS_PER_SECOND,
+                        "ms" => 1_000_000,
+                        "us" | "µs" => 1_000,
+                        _ => 1
+                    };
+
+                    time = time.checked_add(scaled(&value, scale)?).ok_or(DurationError::Overflow)?;
+
+                    continue;
+                }
+            };
+
+            *calendar = count.and_then(|count| calendar.checked_add(count)).ok_or(DurationError::Overflow)?;
+        }
+
+        Duration::from_nanos(months, days, time)
+    }
+}
+
+/// The number of nanoseconds in `value` units of `seconds` seconds each.
+fn nanos(value: &str, seconds: i128) -> Result<i128, DurationError> {
+    scaled(value, seconds * NANOS_PER_SECOND)
+}
+
+/// The number of nanoseconds in `value` units of `scale` nanoseconds each, rounded toward zero.
+fn scaled(value: &str, scale: i128) -> Result<i128, DurationError> {
+    let (integer, fraction) = value.split_once([',', '.']).unwrap_or((value, ""));
+    let fraction = &fraction[..fraction.len().min(18)];
+
+    let integer: i128 = integer.parse().map_err(|_| DurationError::Overflow)?;
+    let fraction = match fraction {
+        "" => 0,
+        digits => digits.parse::<i128>().map_err(|_| DurationError::Overflow)? * scale / 10i128.pow(digits.len() as u32)
+    };
+
+    integer.checked_mul(scale).and_then(|nanos| nanos.checked_add(fraction)).ok_or(DurationError::Overflow)
+}
+
+impl Neg for Duration {
+    type Output = Duration;
+
+    fn neg(self) -> Duration {
+        Duration { months: -self.months, days: -self.days, time: -self.time }
+    }
+}
+
+impl From<TimeDelta> for Duration {
+    fn from(time: TimeDelta) -> Duration {
+        Duration { months: 0, days: 0, time }
+    }
+}
+
+impl FromStr for Duration {
+    type Err = DurationError;
+
+    /// Parse an ISO 8601 duration such as `P1Y2M3DT4H5M6.5S` or `P2W`, or a sequence of numbers
+    /// and units such as `90m`, `2w3d` or `1h 30m`.
+    ///
+    /// The units are `y`, `mo`, `w`, `d`, `h`, `m`, `s`, `ms`, `us` and `ns`. Either form may be
+    /// preceded by a sign.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim();
+        let invalid = || DurationError::Invalid(String::from(input));
+
+        let (negative, duration) = if let Some(captures) = ISO8601.captures(input) {
+            // A designator must be followed by at least one component.
+            if input.ends_with(['P', 'T']) { return Err(invalid()) }
+
+            (captures.name("sign").is_some_and(|sign| sign.as_str() == "-"), Duration::parse_iso8601(&captures)?)
+        } else if let Some(captures) = HUMAN.captures(input) {
+            (captures.name("sign").is_some_and(|sign| sign.as_str() == "-"), Duration::parse_human(input)?)
+        } else {
+            return Err(invalid())
+        };
+
+        Ok(if negative { -duration } else { duration })
+    }
+}
+
+impl fmt::Display for Duration {
+    /// Write the duration in the ISO 8601 format, such as `P1DT2H30M`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let negative = self.months < 0 || self.days < 0 || self.time < TimeDelta::zero();
+        let (months, days) = (self.months.unsigned_abs(), self.days.unsigned_abs());
+        let nanos = i128::from(self.time.num_seconds()) * NANOS_PER_SECOND + i128::from(self.time.subsec_nanos());
+        let nanos = nanos.unsigned_abs();
+
+        if negative { write!(f, "-")? }
+
+        write!(f, "P")?;
+
+        if months >= 12 { write!(f, "{}Y", months / 12)? }
+        if months % 12 > 0 { write!(f, "{}M", months % 12)? }
+        if days > 0 { write!(f, "{}D", days)? }
+
+        if nanos > 0 {
+            let seconds = nanos / NANOS_PER_SECOND as u128;
+
+            write!(f, "T")?;
+
+            if seconds >= 3600 { write!(f, "{}H", seconds / 3600)? }
+            if seconds % 3600 >= 60 { write!(f, "{}M", seconds % 3600 / 60)? }
+
+            match (seconds % 60, nanos % NANOS_PER_SECOND as u128) {
+                (0, 0) => {}
+                (seconds, 0) => write!(f, "{}S", seconds)?,
+                (seconds, fraction) => write!(f, "{}.{}S", seconds, format!("{:09}", fraction).trim_end_matches('0'))?
+            }
+        } else if months == 0 && days == 0 {
+            write!(f, "T0S")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Add `duration` to `datetime`.
+///
+/// The months and days of the duration are added to the date and time shown by clocks in `zone`,
+/// or at the offset of `datetime` if it is [`None`], and then the exact time is added. The result
+/// is in `zone`, or at the offset of `datetime`.
+pub fn add(datetime: &DateTime<FixedOffset>, duration: &Duration, zone: Option<Zone>) -> Result<DateTime<FixedOffset>, DurationError> {
+    let datetime = zone.map_or(*datetime, |zone| zone.convert(datetime));
+
+    let calendar = match (duration.months, duration.days) {
+        (0, 0) => datetime,
+        (months, days) => {
+            let local = add_days(add_months(datetime.naive_local(), months)?, days)?;
+
+            match zone {
+                Some(zone) => zone.localize(&local)?,
+                None => datetime.offset().from_local_datetime(&local).single().ok_or(DurationError::Overflow)?
+            }
+        }
+    };
+
+    let result = calendar.checked_add_signed(duration.time).ok_or(DurationError::Overflow)?;
+
+    Ok(zone.map_or(result, |zone| zone.convert(&result)))
+}
+
+fn add_months(datetime: NaiveDateTime, months: i64) -> Result<NaiveDateTime, DurationError> {
+    let count = Months::new(u32::try_from(months.unsigned_abs()).map_err(|_| DurationError::Overflow)?);
+
+    if months < 0 { datetime.checked_sub_months(count) } else { datetime.checked_add_months(count) }.ok_or(DurationError::Overflow)
+}
+
+fn add_days(datetime: NaiveDateTime, days: i64) -> Result<NaiveDateTime, DurationError> {
+    let count = Days::new(days.unsigned_abs());
+
+    if days < 0 { datetime.checked_sub_days(count) } else { datetime.checked_add_days(count) }.ok_or(DurationError::Overflow)
+}
+
+/// A unit in which differences between timestamps are written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Unit {
+    /// Nanoseconds.
+    #[cfg_attr(feature = "clap", value(name = "ns"))]
+    Nanos,
+
+    /// Microseconds.
+    #[cfg_attr(feature = "clap", value(name = "us"))]
+    Micros,
+
+    /// Milliseconds.
+    #[cfg_attr(feature = "clap", value(name = "ms"))]
+    Millis,
+
+    /// Seconds.
+    #[cfg_attr(feature = "clap", value(name = "s"))]
+    Seconds,
+
+    /// Minutes.
+    #[cfg_attr(feature = "clap", value(name = "m"))]
+    Minutes,
+
+    /// Hours.
+    #[cfg_attr(feature = "clap", value(name = "h"))]
+    Hours,
+
+    /// Days of exactly 24 hours.
+    #[cfg_attr(feature = "clap", value(name = "d"))]
+    Days,
+
+    /// Weeks of exactly seven days.
+    #[cfg_attr(feature = "clap", value(name = "w"))]
+    Weeks
+}
+
+impl Unit {
+    fn nanos(self) -> i128 {
+        match self {
+            Unit::Nanos => 1,
+            Unit::Micros => 1_000,
+            Unit::Millis => 1_000_000,
+            Unit::Seconds => NANOS_PER_SECOND,
+            Unit::Minutes => 60 * NANOS_PER_SECOND,
+            Unit::Hours => 3600 * NANOS_PER_SECOND,
+            Unit::Days => 86_400 * NANOS_PER_SECOND,
+            Unit::Weeks => 604_800 * NANOS_PER_SECOND
+        }
+    }
+}
+
+/// The number of `unit`s in `delta`, which is fractional if `delta` is not a whole number of them.
+pub fn in_unit(delta: TimeDelta, unit: Unit) -> f64 {
+    let nanos = i128::from(delta.num_seconds()) * NANOS_PER_SECOND + i128::from(delta.subsec_nanos());
+
+    (nanos / unit.nanos()) as f64 + (nanos % unit.nanos()) as f64 / unit.nanos() as f64
+}
+
+/// Describe `delta` in days, hours, minutes and seconds, such as `1 day, 12 hours and 30 minutes`.
+///
+/// Negative differences are preceded by a minus sign.
+pub fn humanize(delta: TimeDelta) -> String {
+    let sign = if delta < TimeDelta::zero() { "-" } else { "" };
+    let delta = delta.abs();
+    let seconds = delta.num_seconds();
+
+    let mut parts: Vec<String> = [(seconds / 86_400, "day"), (seconds % 86_400 / 3600, "hour"), (seconds % 3600 / 60, "minute")]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, unit)| format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" }))
+        .collect();
+
+    match (seconds % 60, delta.subsec_nanos()) {
+        (0, 0) if !parts.is_empty() => {}
+        (seconds, 0) => parts.push(format!("{} second{}", seconds, if seconds == 1 { "" } else { "s" })),
+        (seconds, nanos) => parts.push(format!("{}.{} seconds", seconds, format!("{:09}", nanos).trim_end_matches('0')))
+    }
+
+    match parts.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{}{} and {}", sign, rest.join(", "), last),
+        _ => format!("{}{}", sign, parts.concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeDelta};
+    use chrono_tz::Tz;
+
+    use crate::zone::Zone;
+
+    use super::{Duration, DurationError, Unit};
+
+    fn duration(input: &str) -> Duration {
+        input.parse().unwrap()
+    }
+
+    fn add(datetime: &str, duration: &str, zone: Option<Zone>) -> String {
+        let datetime = DateTime::parse_from_rfc3339(datetime).unwrap();
+
+        super::add(&datetime, &self::duration(duration), zone).unwrap().to_rfc3339()
+    }
+
+    #[test]
+    fn can_parse_iso8601_durations() {
+        assert_eq!(duration("P1DT2H"), Duration { months: 0, days: 1, time: TimeDelta::hours(2) });
+        assert_eq!(duration("P1Y2M3W"), Duration { months: 14, days: 21, time: TimeDelta::zero() });
+        assert_eq!(duration("PT1.5S"), Duration::from(TimeDelta::milliseconds(1500)));
+        assert_eq!(duration("-PT0,25H"), Duration::from(TimeDelta::minutes(-15)));
+        assert!(matches!("P".parse::<Duration>(), Err(DurationError::Invalid(..))));
+        assert!(matches!("P1DT".parse::<Duration>(), Err(DurationError::Invalid(..))));
+    }
+
+    #[test]
+    fn can_parse_human_durations() {
+        assert_eq!(duration("90m"), Duration::from(TimeDelta::minutes(90)));
+        assert_eq!(duration("2w3d"), Duration { months: 0, days: 17, time: TimeDelta::zero() });
+        assert_eq!(duration("1h 30m 15.5s"), Duration::from(TimeDelta::milliseconds(5_415_500)));
+        assert_eq!(duration("1y6mo"), Duration { months: 18, days: 0, time: TimeDelta::zero() });
+        assert_eq!(duration("250ms"), Duration::from(TimeDelta::milliseconds(250)));
+        assert_eq!(duration("-36h"), Duration::from(TimeDelta::hours(-36)));
+        assert!(matches!("1.5d".parse::<Duration>(), Err(DurationError::Fraction)));
+        assert!(matches!("soon".parse::<Duration>(), Err(DurationError::Invalid(..))));
+    }
+
+    #[test]
+    fn can_write_iso8601_durations() {
+        assert_eq!(duration("P1Y2M3DT4H5M6.5S").to_string(), "P1Y2M3DT4H5M6.5S");
+        assert_eq!(duration("36h").to_string(), "PT36H");
+        assert_eq!(duration("-90s").to_string(), "-PT1M30S");
+        assert_eq!(Duration::default().to_string(), "PT0S");
+    }
+
+    #[test]
+    fn can_add_durations() {
+        assert_eq!(add("2024-01-01T00:00:00Z", "36h", None), "2024-01-02T12:00:00+00:00");
+        assert_eq!(add("2024-01-31T12:00:00+01:00", "P1M", None), "2024-02-29T12:00:00+01:00");
+        assert_eq!(add("2024-03-01T00:00:00Z", "-1d", None), "2024-02-29T00:00:00+00:00");
+    }
+
+    #[test]
+    fn adds_days_across_changes_of_offset() {
+        let zone = Some(Zone::Iana(Tz::America__New_York));
+
+        assert_eq!(add("2024-03-09T17:00:00Z", "1d", zone), "2024-03-10T12:00:00-04:00");
+        assert_eq!(add("2024-03-09T17:00:00Z", "24h", zone), "2024-03-10T13:00:00-04:00");
+    }
+
+    #[test]
+    fn can_describe_differences() {
+        assert_eq!(super::in_unit(TimeDelta::minutes(90), Unit::Hours), 1.5);
+        assert_eq!(super::in_unit(TimeDelta::days(-14), Unit::Weeks), -2.0);
+        assert_eq!(super::humanize(TimeDelta::seconds(131_400)), "1 day, 12 hours and 30 minutes");
+        assert_eq!(super::humanize(TimeDelta::milliseconds(-61_500)), "-1 minute and 1.5 seconds");
+        assert_eq!(super::humanize(TimeDelta::zero()), "0 seconds");
+    }
+}
@@ -1,61 +1,273 @@
 //! Time and date library logic.
 
+use chrono::format::StrftimeItems;
+use chrono::{DateTime, Datelike, FixedOffset, SecondsFormat, SubsecRound};
+use thiserror::Error;
+
+pub mod duration;
+pub mod parse;
+pub mod zone;
+
 const FMT: &str = "%F %T";
 
+#[derive(Debug, Error)]
+pub enum FormatError {
+    #[error("The strftime pattern '{0}' is invalid.")]
+    Pattern(String),
+
+    #[error("RFC 2822 can only write the years 0 to 9999, not {0}.")]
+    Rfc2822Year(i32)
+}
+
+/// A format in which timestamps are written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// The date and time separated by a space, such as `2024-01-01 12:00:00`.
+    Simple,
+
+    /// ISO 8601 in the extended format, such as `2024-01-01T12:00:00Z`.
+    Iso8601,
+
+    /// ISO 8601 in the basic format, such as `20240101T120000Z`.
+    Iso8601Basic,
+
+    /// RFC 2822, such as `Mon, 1 Jan 2024 12:00:00 +0000`. Fractions of a second are never written.
+    Rfc2822,
+
+    /// RFC 3339, such as `2024-01-01T12:00:00+00:00`.
+    Rfc3339,
+
+    /// Seconds since the Unix epoch.
+    Unix,
+
+    /// Milliseconds since the Unix epoch.
+    UnixMillis,
+
+    /// Microseconds since the Unix epoch.
+    UnixMicros,
+
+    /// Nanoseconds since the Unix epoch.
+    UnixNanos,
+
+    /// A pattern of `strftime`-style specifiers, such as `%Y-%m-%d %H:%M`.
+    Strftime(String)
+}
+
+impl Format {
+    /// A format of `strftime`-style specifiers, if every specifier in `pattern` is valid.
+    pub fn strftime(pattern: &str) -> Result<Self, FormatError> {
+        StrftimeItems::new(pattern).parse().map_err(|_| FormatError::Pattern(pattern.to_string()))?;
+
+        Ok(Format::Strftime(pattern.to_string()))
+    }
+}
+
+/// The precision to which timestamps are written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Precision {
+    /// Whole seconds.
+    #[cfg_attr(feature = "clap", value(name = "s"))]
+    Seconds,
+
+    /// Milliseconds.
+    #[cfg_attr(feature = "clap", value(name = "ms"))]
+    Millis,
+
+    /// Microseconds.
+    #[cfg_attr(feature = "clap", value(name = "us"))]
+    Micros,
+
+    /// Nanoseconds.
+    #[cfg_attr(feature = "clap", value(name = "ns"))]
+    Nanos
+}
+
+impl Precision {
+    /// The number of digits after the decimal point.
+    pub fn digits(self) -> u16 {
+        match self {
+            Precision::Seconds => 0,
+            Precision::Millis => 3,
+            Precision::Micros => 6,
+            Precision::Nanos => 9
+        }
+    }
+}
+
+/// Write `datetime` in `format`.
+///
+/// The timestamp is truncated to `precision`, and written with exactly that many fractional digits.
+/// Without a precision, Unix timestamps are written as integers, the simple format omits fractions of
+/// a second, and other formats write as many fractional digits as needed, in groups of three.
+pub fn format(datetime: &DateTime<FixedOffset>, format: &Format, precision: Option<Precision>) -> Result<String, FormatError> {
+    let datetime = precision.map_or(*datetime, |precision| datetime.trunc_subsecs(precision.digits()));
+
+    let fraction = match precision {
+        None => "%.f",
+        Some(Precision::Seconds) => "",
+        Some(Precision::Millis) => "%.3f",
+        Some(Precision::Micros) => "%.6f",
+        Some(Precision::Nanos) => "%.9f"
+    };
+
+    // ISO 8601 designates UTC with Z rather than a zero offset.
+    let utc = datetime.offset().local_minus_utc() == 0;
+
+    let formatted = match format {
+        Format::Simple if precision.is_none() => datetime.format(FMT).to_string(),
+        Format::Simple => datetime.format(&format!("{}{}", FMT, fraction)).to_string(),
+        Format::Iso8601 => datetime.format(&format!("%Y-%m-%dT%H:%M:%S{}{}", fraction, if utc { "Z" } else { "%:z" })).to_string(),
+        Format::Iso8601Basic => datetime.format(&format!("%Y%m%dT%H%M%S{}{}", fraction, if utc { "Z" } else { "%z" })).to_string(),
+        Format::Rfc2822 => match datetime.year() {
+            0..=9999 => datetime.to_rfc2822(),
+            year => return Err(FormatError::Rfc2822Year(year))
+        },
+        Format::Rfc3339 => {
+            let seconds = match precision {
+                None => SecondsFormat::AutoSi,
+                Some(Precision::Seconds) => SecondsFormat::Secs,
+                Some(Precision::Millis) => SecondsFormat::Millis,
+                Some(Precision::Micros) => SecondsFormat::Micros,
+                Some(Precision::Nanos) => SecondsFormat::Nanos
+            };
+
+            datetime.to_rfc3339_opts(seconds, false)
+        }
+        Format::Unix => unix::format(&datetime, Precision::Seconds, precision),
+        Format::UnixMillis => unix::format(&datetime, Precision::Millis, precision),
+        Format::UnixMicros => unix::format(&datetime, Precision::Micros, precision),
+        Format::UnixNanos => unix::format(&datetime, Precision::Nanos, precision),
+        Format::Strftime(pattern) => {
+            let items = StrftimeItems::new(pattern).parse().map_err(|_| FormatError::Pattern(pattern.clone()))?;
+
+            datetime.format_with_items(items.into_iter()).to_string()
+        }
+    };
+
+    Ok(formatted)
+}
+
 /// UTC
 pub mod utc {
-    use chrono::Utc;
+    use chrono::{DateTime, Datelike, FixedOffset, Utc};
 
-    pub fn now() -> Vec<u8> {
-        let now = Utc::now();
+    /// The current time in UTC.
+    pub fn now() -> DateTime<FixedOffset> {
+        Utc::now().fixed_offset()
+    }
 
-        now.format(super::FMT).to_string().into_bytes()
+    /// The current year in UTC.
+    pub fn year() -> i32 {
+        Utc::now().year()
     }
+}
 
-    pub fn now_iso8601() -> Vec<u8> {
-        let now = Utc::now();
+/// Localized Timezone
+pub mod local {
+    use chrono::{DateTime, FixedOffset, Local};
 
-        now.to_rfc3339().into_bytes()
+    /// The current time in the local timezone.
+    pub fn now() -> DateTime<FixedOffset> {
+        Local::now().fixed_offset()
     }
+}
 
-    pub fn now_rfc3339() -> Vec<u8> {
-        let now = Utc::now();
+/// Unix Time
+pub mod unix {
+    use chrono::{DateTime, FixedOffset, Utc};
+    use thiserror::Error;
 
-        now.to_rfc3339().into_bytes()
+    use super::Precision;
+
+    #[derive(Debug, Error)]
+    #[error("The system clock is set before the Unix epoch.")]
+    pub struct ClockError;
+
+    /// The number of milliseconds elapsed since the Unix epoch.
+    pub fn now_millis() -> Result<u64, ClockError> {
+        u64::try_from(Utc::now().timestamp_millis()).map_err(|_| ClockError)
     }
 
-    pub fn now_rfc2822() -> Vec<u8> {
-        let now = Utc::now();
+    /// Write the time elapsed since the Unix epoch in units of `unit`, with the fractional digits
+    /// needed for `precision`, if it is finer than the unit.
+    pub(crate) fn format(datetime: &DateTime<FixedOffset>, unit: Precision, precision: Option<Precision>) -> String {
+        let nanos = i128::from(datetime.timestamp()) * 1_000_000_000 + i128::from(datetime.timestamp_subsec_nanos());
+        let sign = if nanos < 0 { "-" } else { "" };
+        let scale = 10u128.pow(9 - u32::from(unit.digits()));
+        let (integer, remainder) = (nanos.unsigned_abs() / scale, nanos.unsigned_abs() % scale);
 
-        now.to_rfc2822().into_bytes()
+        match precision.map_or(0, |precision| precision.digits().saturating_sub(unit.digits())) {
+            0 => format!("{}{}", sign, integer),
+            digits => {
+                let fraction = format!("{:0width$}", remainder, width = 9 - usize::from(unit.digits()));
+
+                format!("{}{}.{}", sign, integer, &fraction[..usize::from(digits)])
+            }
+        }
     }
 }
 
-/// Localized Timezone
-pub mod local {
-    use chrono::Local;
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::{Format, FormatError, Precision};
 
-    pub fn now() -> Vec<u8> {
-        let now = Local::now();
+    fn format(format: Format, precision: Option<Precision>) -> String {
+        let datetime = DateTime::parse_from_rfc3339("2024-01-01T12:00:00.123456789Z").unwrap();
 
-        now.format(super::FMT).to_string().into_bytes()
+        super::format(&datetime, &format, precision).unwrap()
     }
 
-    pub fn now_iso8601() -> Vec<u8> {
-        let now = Local::now();
+    #[test]
+    fn can_format_iso8601() {
+        assert_eq!(format(Format::Iso8601, None), "2024-01-01T12:00:00.123456789Z");
+        assert_eq!(format(Format::Iso8601, Some(Precision::Seconds)), "2024-01-01T12:00:00Z");
+        assert_eq!(format(Format::Iso8601Basic, Some(Precision::Millis)), "20240101T120000.123Z");
+
+        let datetime = DateTime::parse_from_rfc3339("2024-01-01T12:00:00+05:30").unwrap();
 
-        now.to_rfc3339().into_bytes()
+        assert_eq!(super::format(&datetime, &Format::Iso8601, None).unwrap(), "2024-01-01T12:00:00+05:30");
+        assert_eq!(super::format(&datetime, &Format::Iso8601Basic, None).unwrap(), "20240101T120000+0530");
     }
 
-    pub fn now_rfc3339() -> Vec<u8> {
-        let now = Local::now();
+    #[test]
+    fn can_format_rfc2822_and_rfc3339() {
+        assert_eq!(format(Format::Rfc2822, None), "Mon, 1 Jan 2024 12:00:00 +0000");
+        assert_eq!(format(Format::Rfc3339, None), "2024-01-01T12:00:00.123456789+00:00");
+        assert_eq!(format(Format::Rfc3339, Some(Precision::Micros)), "2024-01-01T12:00:00.123456+00:00");
 
-        now.to_rfc3339().into_bytes()
+        let datetime = DateTime::from_timestamp(-99_999_999_999, 0).unwrap().fixed_offset();
+
+        assert!(matches!(super::format(&datetime, &Format::Rfc2822, None), Err(FormatError::Rfc2822Year(-1199))));
+    }
+
+    #[test]
+    fn can_format_simple_timestamps() {
+        assert_eq!(format(Format::Simple, None), "2024-01-01 12:00:00");
+        assert_eq!(format(Format::Simple, Some(Precision::Millis)), "2024-01-01 12:00:00.123");
     }
 
-    pub fn now_rfc2822() -> Vec<u8> {
-        let now = Local::now();
+    #[test]
+    fn can_format_unix_timestamps() {
+        assert_eq!(format(Format::Unix, None), "1704110400");
+        assert_eq!(format(Format::Unix, Some(Precision::Millis)), "1704110400.123");
+        assert_eq!(format(Format::UnixMillis, None), "1704110400123");
+        assert_eq!(format(Format::UnixMicros, Some(Precision::Nanos)), "1704110400123456.789");
+        assert_eq!(format(Format::UnixNanos, Some(Precision::Seconds)), "1704110400000000000");
+
+        let datetime = DateTime::parse_from_rfc3339("1969-12-31T23:59:58.5Z").unwrap();
+
+        assert_eq!(super::format(&datetime, &Format::Unix, Some(Precision::Millis)).unwrap(), "-1.500");
+    }
 
-        now.to_rfc2822().into_bytes()
+    #[test]
+    fn can_format_strftime_patterns() {
+        assert_eq!(format(Format::Strftime(String::from("%d/%m/%Y %H:%M")), None), "01/01/2024 12:00");
+        assert!(matches!(
+            super::format(&DateTime::UNIX_EPOCH.fixed_offset(), &Format::Strftime(String::from("%Q")), None),
+            Err(FormatError::Pattern(..))
+        ));
     }
 }
@@ -0,0 +1,356 @@
+//! Parsing of timestamps in the formats found in protocols and log files.
+
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday};
+use regex::{Captures, Regex};
+use thiserror::Error;
+
+use crate::zone::{Zone, ZoneError};
+
+static UNIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?<sign>[+-])?(?<integer>[0-9]+)(?:\.(?<fraction>[0-9]{1,9}))?(?<unit>s|ms|us|µs|ns)?$").unwrap()
+});
+
+static ISO8601: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"^(?<year>[+-]?[0-9]{4})",
+        r"(?:-(?<month>[0-9]{2})-(?<day>[0-9]{2})|(?<basic_month>[0-9]{2})(?<basic_day>[0-9]{2})",
+        r"|-?W(?<week>[0-9]{2})(?:-?(?<weekday>[1-7]))?|-?(?<ordinal>[0-9]{3}))",
+        r"(?:[T ](?<hour>[0-9]{2})(?::?(?<minute>[0-9]{2})(?::?(?<second>[0-9]{2})(?:[.,](?<fraction>[0-9]{1,9}))?)?)?",
+        r"(?<zone>Z|[+-][0-9]{2}(?::?[0-9]{2})?)?)?$"
+    ))
+    .unwrap()
+});
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("The timestamp does not match any supported format.")]
+    NoMatch,
+
+    #[error("The timestamp is not a valid {0} timestamp.")]
+    Invalid(&'static str),
+
+    #[error("The timestamp is out of range.")]
+    Overflow,
+
+    #[error(transparent)]
+    Zone(#[from] ZoneError)
+}
+
+/// A format in which timestamps are read.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Format {
+    /// RFC 3339, such as `2024-01-01T12:00:00Z`.
+    #[cfg_attr(feature = "clap", value(name = "rfc3339"))]
+    Rfc3339,
+
+    /// RFC 2822, as used in email headers, such as `Mon, 01 Jan 2024 12:00:00 +0000`.
+    #[cfg_attr(feature = "clap", value(name = "rfc2822"))]
+    Rfc2822,
+
+    /// ISO 8601 calendar, week or ordinal dates, such as `2024-01-01`, `2024-W01-1` or `2024-001`,
+    /// in the basic or extended format, with an optional time and offset.
+    #[cfg_attr(feature = "clap", value(name = "iso8601"))]
+    Iso8601,
+
+    /// Seconds since the Unix epoch, optionally with a fraction.
+    Unix,
+
+    /// Milliseconds since the Unix epoch.
+    #[cfg_attr(feature = "clap", value(name = "unix-ms"))]
+    UnixMillis,
+
+    /// Microseconds since the Unix epoch.
+    #[cfg_attr(feature = "clap", value(name = "unix-us"))]
+    UnixMicros,
+
+    /// Nanoseconds since the Unix epoch.
+    #[cfg_attr(feature = "clap", value(name = "unix-ns"))]
+    UnixNanos,
+
+    /// The Common Log Format of the Apache and nginx access logs, such as `10/Oct/2000:13:55:36 -0700`.
+    Clf,
+
+    /// The BSD syslog format of RFC 3164, such as `Oct 11 22:14:15`, which omits the year.
+    Syslog
+}
+
+impl Format {
+    /// The formats in the order in which they are tried when detecting the format of a timestamp.
+    pub const ALL: [Format; 9] = [
+        Format::Unix,
+        Format::UnixMillis,
+        Format::UnixMicros,
+        Format::UnixNanos,
+        Format::Rfc3339,
+        Format::Iso8601,
+        Format::Rfc2822,
+        Format::Clf,
+        Format::Syslog
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Rfc3339 => "RFC 3339",
+            Format::Rfc2822 => "RFC 2822",
+            Format::Iso8601 => "ISO 8601",
+            Format::Unix => "Unix",
+            Format::UnixMillis => "Unix millisecond",
+            Format::UnixMicros => "Unix microsecond",
+            Format::UnixNanos => "Unix nanosecond",
+            Format::Clf => "Common Log Format",
+            Format::Syslog => "syslog"
+        }
+    }
+
+    /// The number of nanoseconds in a unit of a Unix timestamp.
+    fn unit(self) -> Option<i128> {
+        match self {
+            Format::Unix => Some(1_000_000_000),
+            Format::UnixMillis => Some(1_000_000),
+            Format::UnixMicros => Some(1_000),
+            Format::UnixNanos => Some(1),
+            _ => None
+        }
+    }
+}
+
+/// Parse a timestamp in `format`, or if it is [`None`], in the first format which it matches.
+///
+/// Integers are Unix timestamps whose unit is given by a suffix (`s`, `ms`, `us` or `ns`), by
+/// `format`, or when detected, by their magnitude: the unit which places the timestamp between the
+/// years 1973 and 5138, or before 5138 if it is in seconds. ISO 8601 basic dates such as
+/// `20240101` are therefore read as dates only when `format` is [`Format::Iso8601`].
+///
+/// Timestamps without an offset are in `zone`, and syslog timestamps are in its current year.
+pub fn parse(input: &str, format: Option<Format>, zone: Zone) -> Result<(DateTime<FixedOffset>, Format), ParseError> {
+    parse_in_year(input, format, zone, zone.now().year())
+}
+
+/// Parse a timestamp as [`parse`] does, or take the current time if it is `now`.
+///
+/// Timestamps without an offset are in `zone`, or in UTC, and the current time is in `zone`, or in
+/// the local time zone.
+pub fn parse_or_now(input: &str, zone: Option<Zone>) -> Result<DateTime<FixedOffset>, ParseError> {
+    match input.trim() {
+        now if now.eq_ignore_ascii_case("now") => Ok(zone.unwrap_or(Zone::Local).now()),
+        input => parse(input, None, zone.unwrap_or(Zone::Utc)).map(|(datetime, _)| datetime)
+    }
+}
+
+/// Parse a timestamp as [`parse`] does, with syslog timestamps in `year`.
+pub fn parse_in_year(input: &str, format: Option<Format>, zone: Zone, year: i32) -> Result<(DateTime<FixedOffset>, Format), ParseError> {
+    let input = input.trim();
+
+    match format {
+        Some(format) => parse_format(input, format, zone, year)
+            .ok_or(ParseError::Invalid(format.name()))?
+            .map(|datetime| (datetime, format)),
+        None => {
+            // Unix timestamps are detected by their form, then assigned a unit by their magnitude.
+            if UNIX.is_match(input) { return parse_unix(input, None) }
+
+            Format::ALL.into_iter()
+                .filter(|format| format.unit().is_none())
+                .find_map(|format| parse_format(input, format, zone, year).map(|result| result.map(|datetime| (datetime, format))))
+                .ok_or(ParseError::NoMatch)?
+        }
+    }
+}
+
+/// Parse `input` in `format`, returning [`None`] if it is not in the format, or an error if it is
+/// but does not describe a representable instant.
+fn parse_format(input: &str, format: Format, zone: Zone, year: i32) -> Option<Result<DateTime<FixedOffset>, ParseError>> {
+    match format {
+        Format::Unix | Format::UnixMillis | Format::UnixMicros | Format::UnixNanos => {
+            UNIX.is_match(input).then(|| parse_unix(input, Some(format)).map(|(datetime, _)| datetime))
+        }
+        Format::Rfc3339 => DateTime::parse_from_rfc3339(input).ok().map(Ok),
+        Format::Rfc2822 => DateTime::parse_from_rfc2822(input).ok().map(Ok),
+        Format::Iso8601 => ISO8601.captures(input).map(|captures| parse_iso8601(&captures, zone)),
+        Format::Clf => {
+            let input = input.strip_prefix('[').and_then(|input| input.strip_suffix(']')).unwrap_or(input);
+
+            DateTime::parse_from_str(input, "%d/%b/%Y:%H:%M:%S %z").ok().map(Ok)
+        }
+        Format::Syslog => {
+            let input = input.split_whitespace().collect::<Vec<_>>().join(" ");
+
+            NaiveDateTime::parse_from_str(&format!("{} {}", year, input), "%Y %b %d %H:%M:%S%.f")
+                .ok()
+                .map(|datetime| zone.localize(&datetime).map_err(ParseError::from))
+        }
+    }
+}
+
+fn parse_unix(input: &str, format: Option<Format>) -> Result<(DateTime<FixedOffset>, Format), ParseError> {
+    let captures = UNIX.captures(input).ok_or(ParseError::NoMatch)?;
+    let integer: i128 = captures["integer"].parse().map_err(|_| ParseError::Overflow)?;
+
+    let format = match captures.name("unit").map(|unit| unit.as_str()) {
+        Some("s") => Format::Unix,
+        Some("ms") => Format::UnixMillis,
+        Some("us" | "µs") => Format::UnixMicros,
+        Some(_) => Format::UnixNanos,
+        None => format.unwrap_or(match integer {
+            0..100_000_000_000 => Format::Unix,
+            100_000_000_000..100_000_000_000_000 => Format::UnixMillis,
+            100_000_000_000_000..100_000_000_000_000_000 => Format::UnixMicros,
+            _ => Format::UnixNanos
+        })
+    };
+
+    let unit = format.unit().unwrap_or(1);
+    let fraction = captures.name("fraction").map_or(0, |fraction| {
+        let digits = fraction.as_str();
+
+        digits.parse::<i128>().unwrap_or(0) * unit / 10i128.pow(digits.len() as u32)
+    });
+
+    let nanos = integer.checked_mul(unit).and_then(|nanos| nanos.checked_add(fraction)).ok_or(ParseError::Overflow)?;
+    let nanos = if captures.name("sign").is_some_and(|sign| sign.as_str() == "-") { -nanos } else { nanos };
+
+    let seconds = i64::try_from(nanos.div_euclid(1_000_000_000)).map_err(|_| ParseError::Overflow)?;
+    let datetime = DateTime::from_timestamp(seconds, nanos.rem_euclid(1_000_000_000) as u32).ok_or(ParseError::Overflow)?;
+
+    Ok((datetime.fixed_offset(), format))
+}
+
+fn parse_iso8601(captures: &Captures, zone: Zone) -> Result<DateTime<FixedOffset>, ParseError> {
+    let invalid = || ParseError::Invalid(Format::Iso8601.name());
+    let number = |name: &str| captures.name(name).map(|value| value.as_str().parse::<u32>().map_err(|_| invalid())).transpose();
+
+    let year: i32 = captures["year"].parse().map_err(|_| invalid())?;
+
+    let date = match (number("month")?.or(number("basic_month")?), number("day")?.or(number("basic_day")?), number("week")?, number("ordinal")?) {
+        (Some(month), Some(day), ..) => NaiveDate::from_ymd_opt(year, month, day),
+        (.., Some(week), _) => {
+            let weekday = number("weekday")?.map_or(Some(Weekday::Mon), |day| Weekday::try_from(day as u8 - 1).ok());
+
+            weekday.and_then(|weekday| NaiveDate::from_isoywd_opt(year, week, weekday))
+        }
+        (.., Some(ordinal)) => NaiveDate::from_yo_opt(year, ordinal),
+        _ => None
+    }
+    .ok_or_else(invalid)?;
+
+    let nanos = captures.name("fraction").map_or(0, |fraction| {
+        let digits = fraction.as_str();
+
+        digits.parse::<u32>().unwrap_or(0) * 10u32.pow(9 - digits.len() as u32)
+    });
+
+    let time = NaiveTime::from_hms_nano_opt(
+        number("hour")?.unwrap_or(0),
+        number("minute")?.unwrap_or(0),
+        number("second")?.unwrap_or(0),
+        nanos
+    )
+    .ok_or_else(invalid)?;
+
+    let offset = match captures.name("zone").map(|zone| zone.as_str()) {
+        None => return Ok(zone.localize(&date.and_time(time))?),
+        Some("Z") => FixedOffset::east_opt(0),
+        Some(zone) => {
+            let sign = if zone.starts_with('-') { -1 } else { 1 };
+            let digits: String = zone[1..].chars().filter(char::is_ascii_digit).collect();
+            let hours: i32 = digits[..2].parse().map_err(|_| invalid())?;
+            let minutes: i32 = digits.get(2..).filter(|minutes| !minutes.is_empty()).map_or(Ok(0), str::parse).map_err(|_| invalid())?;
+
+            FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+        }
+    }
+    .ok_or_else(invalid)?;
+
+    offset.from_local_datetime(&date.and_time(time)).single().ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+
+    use crate::zone::{Zone, ZoneError};
+
+    use super::{Format, ParseError};
+
+    fn parse(input: &str) -> (String, Format) {
+        let (datetime, format) = super::parse_in_year(input, None, Zone::Utc, 2024).unwrap();
+
+        (datetime.to_rfc3339(), format)
+    }
+
+    fn parse_as(input: &str, format: Format) -> String {
+        super::parse_in_year(input, Some(format), Zone::Utc, 2024).unwrap().0.to_rfc3339()
+    }
+
+    #[test]
+    fn can_parse_rfc3339_and_rfc2822() {
+        assert_eq!(parse("2024-01-01T12:00:00.5+01:00"), (String::from("2024-01-01T12:00:00.500+01:00"), Format::Rfc3339));
+        assert_eq!(parse("Mon, 01 Jan 2024 12:00:00 +0000"), (String::from("2024-01-01T12:00:00+00:00"), Format::Rfc2822));
+    }
+
+    #[test]
+    fn can_parse_iso8601_calendar_dates() {
+        assert_eq!(parse("2024-01-01"), (String::from("2024-01-01T00:00:00+00:00"), Format::Iso8601));
+        assert_eq!(parse("2024-01-01 12:30"), (String::from("2024-01-01T12:30:00+00:00"), Format::Iso8601));
+        assert_eq!(parse("20240101T123045,25-0230").0, "2024-01-01T12:30:45.250-02:30");
+        assert_eq!(parse_as("20240101", Format::Iso8601), "2024-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn can_parse_iso8601_week_and_ordinal_dates() {
+        assert_eq!(parse("2024-W01-1").0, "2024-01-01T00:00:00+00:00");
+        assert_eq!(parse("2020W537T08:00Z").0, "2021-01-03T08:00:00+00:00");
+        assert_eq!(parse("2024-W10").0, "2024-03-04T00:00:00+00:00");
+        assert_eq!(parse("2024-366").0, "2024-12-31T00:00:00+00:00");
+        assert_eq!(parse("2023060T1200").0, "2023-03-01T12:00:00+00:00");
+    }
+
+    #[test]
+    fn detects_the_unit_of_unix_timestamps() {
+        assert_eq!(parse("1704067200"), (String::from("2024-01-01T00:00:00+00:00"), Format::Unix));
+        assert_eq!(parse("1704067200123"), (String::from("2024-01-01T00:00:00.123+00:00"), Format::UnixMillis));
+        assert_eq!(parse("1704067200123456"), (String::from("2024-01-01T00:00:00.123456+00:00"), Format::UnixMicros));
+        assert_eq!(parse("1704067200123456789"), (String::from("2024-01-01T00:00:00.123456789+00:00"), Format::UnixNanos));
+    }
+
+    #[test]
+    fn can_parse_unix_timestamps_with_units_and_fractions() {
+        assert_eq!(parse("1704067200.5").0, "2024-01-01T00:00:00.500+00:00");
+        assert_eq!(parse("-1.5").0, "1969-12-31T23:59:58.500+00:00");
+        assert_eq!(parse("1704067200000ms").0, "2024-01-01T00:00:00+00:00");
+        assert_eq!(parse("86400us").0, "1970-01-01T00:00:00.086400+00:00");
+        assert_eq!(parse_as("1704067200", Format::UnixMillis), "1970-01-20T17:21:07.200+00:00");
+    }
+
+    #[test]
+    fn can_parse_log_timestamps() {
+        assert_eq!(parse("[10/Oct/2000:13:55:36 -0700]"), (String::from("2000-10-10T13:55:36-07:00"), Format::Clf));
+        assert_eq!(parse("Oct  8 22:14:15"), (String::from("2024-10-08T22:14:15+00:00"), Format::Syslog));
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        assert!(matches!(super::parse_in_year("yesterday", None, Zone::Utc, 2024), Err(ParseError::NoMatch)));
+        assert!(matches!(super::parse_in_year("2024-02-30", None, Zone::Utc, 2024), Err(ParseError::Invalid("ISO 8601"))));
+        assert!(matches!(super::parse_in_year("2024-01-01", Some(Format::Rfc2822), Zone::Utc, 2024), Err(ParseError::Invalid("RFC 2822"))));
+        assert!(matches!(super::parse_in_year("99999999999999999999999", None, Zone::Utc, 2024), Err(ParseError::Overflow)));
+    }
+
+    #[test]
+    fn reads_timestamps_without_an_offset_in_the_given_zone() {
+        let zone = Zone::Iana(Tz::Europe__Berlin);
+        let parse = |input| super::parse_in_year(input, None, zone, 2024).unwrap().0.to_rfc3339();
+
+        assert_eq!(parse("2024-07-01 12:00"), "2024-07-01T12:00:00+02:00");
+        assert_eq!(parse("Jan  8 22:14:15"), "2024-01-08T22:14:15+01:00");
+        assert_eq!(parse("2024-07-01T12:00:00Z"), "2024-07-01T12:00:00+00:00");
+        assert_eq!(parse("1704067200"), "2024-01-01T00:00:00+00:00");
+        assert!(matches!(
+            super::parse_in_year("2024-03-31 02:30", None, zone, 2024),
+            Err(ParseError::Zone(ZoneError::Skipped(..)))
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+//! Time zones from the IANA time zone database, which is embedded in the program.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, Local, LocalResult, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz, TZ_VARIANTS};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ZoneError {
+    #[error("'{0}' is not a time zone in the IANA time zone database.")]
+    Unknown(String),
+
+    #[error("{0} does not occur in {1}, as the clocks were set forward.")]
+    Skipped(NaiveDateTime, Zone)
+}
+
+/// A time zone in which timestamps are read and written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Zone {
+    Utc,
+
+    /// The time zone of the system.
+    Local,
+
+    /// A zone of the IANA time zone database, such as `America/New_York`.
+    Iana(Tz)
+}
+
+impl Zone {
+    /// The current time in the zone.
+    pub fn now(self) -> DateTime<FixedOffset> {
+        self.convert(&Utc::now().fixed_offset())
+    }
+
+    /// The same instant as `datetime`, with the offset in effect in the zone at that instant.
+    pub fn convert(self, datetime: &DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+        match self {
+            Zone::Utc => datetime.to_utc().fixed_offset(),
+            Zone::Local => datetime.with_timezone(&Local).fixed_offset(),
+            Zone::Iana(tz) => datetime.with_timezone(&tz).fixed_offset()
+        }
+    }
+
+    /// The instant at which clocks in the zone show `datetime`.
+    ///
+    /// When the clocks are set back and show the time twice, the earlier instant is chosen.
+    pub fn localize(self, datetime: &NaiveDateTime) -> Result<DateTime<FixedOffset>, ZoneError> {
+        let localized = match self {
+            Zone::Utc => LocalResult::Single(datetime.and_utc().fixed_offset()),
+            Zone::Local => Local.from_local_datetime(datetime).map(|datetime| datetime.fixed_offset()),
+            Zone::Iana(tz) => tz.from_local_datetime(datetime).map(|datetime| datetime.fixed_offset())
+        };
+
+        localized.earliest().ok_or(ZoneError::Skipped(*datetime, self))
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Zone::Utc => write!(f, "UTC"),
+            Zone::Local => write!(f, "the local time zone"),
+            Zone::Iana(tz) => write!(f, "{}", tz.name())
+        }
+    }
+}
+
+impl FromStr for Zone {
+    type Err = ZoneError;
+
+    /// Parse `utc`, `local`, or the name of a zone in the IANA time zone database, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            zone if zone.eq_ignore_ascii_case("utc") || zone.eq_ignore_ascii_case("z") => Ok(Zone::Utc),
+            zone if zone.eq_ignore_ascii_case("local") => Ok(Zone::Local),
+            zone => TZ_VARIANTS.iter()
+                .find(|tz| tz.name().eq_ignore_ascii_case(zone))
+                .map(|tz| Zone::Iana(*tz))
+                .ok_or_else(|| ZoneError::Unknown(String::from(zone)))
+        }
+    }
+}
+
+/// The state of a zone of the IANA time zone database at an instant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneInfo {
+    pub name: &'static str,
+
+    /// The offset from UTC.
+    pub offset: FixedOffset,
+
+    /// The abbreviation of the offset, such as `EST`, if the zone has one.
+    pub abbreviation: Option<String>,
+
+    /// Whether daylight saving time is in effect.
+    pub dst: bool
+}
+
+/// The state of every zone of the IANA time zone database at `instant`, in alphabetical order.
+pub fn zones(instant: &DateTime<FixedOffset>) -> Vec<ZoneInfo> {
+    TZ_VARIANTS.iter()
+        .map(|tz| {
+            let offset = tz.offset_from_utc_datetime(&instant.naive_utc());
+
+            ZoneInfo {
+                name: tz.name(),
+                offset: offset.fix(),
+                abbreviation: offset.abbreviation().map(String::from),
+                dst: !offset.dst_offset().is_zero()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate};
+    use chrono_tz::Tz;
+
+    use super::{Zone, ZoneError};
+
+    #[test]
+    fn can_parse_zones() {
+        assert_eq!("UTC".parse::<Zone>().unwrap(), Zone::Utc);
+        assert_eq!("local".parse::<Zone>().unwrap(), Zone::Local);
+        assert_eq!("america/new_york".parse::<Zone>().unwrap(), Zone::Iana(Tz::America__New_York));
+        assert!(matches!("Mars/Olympus_Mons".parse::<Zone>(), Err(ZoneError::Unknown(..))));
+    }
+
+    #[test]
+    fn can_convert_between_zones() {
+        let datetime = DateTime::parse_from_rfc3339("2024-07-01T12:00:00Z").unwrap();
+
+        assert_eq!(Zone::Iana(Tz::America__New_York).convert(&datetime).to_rfc3339(), "2024-07-01T08:00:00-04:00");
+        assert_eq!(Zone::Iana(Tz::Asia__Kolkata).convert(&datetime).to_rfc3339(), "2024-07-01T17:30:00+05:30");
+        assert_eq!(Zone::Utc.convert(&Zone::Iana(Tz::Asia__Tokyo).convert(&datetime)), datetime);
+    }
+
+    #[test]
+    fn can_localize_times_around_transitions() {
+        let zone = Zone::Iana(Tz::America__New_York);
+        let time = |hour| NaiveDate::from_ymd_opt(2024, 11, 3).unwrap().and_hms_opt(hour, 30, 0).unwrap();
+
+        assert_eq!(zone.localize(&time(1)).unwrap().to_rfc3339(), "2024-11-03T01:30:00-04:00");
+
+        let skipped = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap().and_hms_opt(2, 30, 0).unwrap();
+
+        assert!(matches!(zone.localize(&skipped), Err(ZoneError::Skipped(..))));
+    }
+
+    #[test]
+    fn reports_daylight_saving_time() {
+        let instant = DateTime::parse_from_rfc3339("2024-07-01T12:00:00Z").unwrap();
+        let zones = super::zones(&instant);
+        let zone = |name| zones.iter().find(|zone| zone.name == name).unwrap();
+
+        assert!(zone("Europe/London").dst);
+        assert_eq!(zone("Europe/London").abbreviation.as_deref(), Some("BST"));
+        assert_eq!(zone("Europe/London").offset.local_minus_utc(), 3600);
+        assert!(!zone("Asia/Tokyo").dst);
+    }
+}
@@ -1,21 +1,72 @@
 pub mod time {
+    #[doc(inline)]
+    pub use grad_time::{format, Format, FormatError, Precision};
     #[doc(inline)]
     pub use grad_time::utc as utc;
     #[doc(inline)]
     pub use grad_time::local as local;
+    #[doc(inline)]
+    pub use grad_time::unix as unix;
+    #[doc(inline)]
+    pub use grad_time::parse as parse;
+    #[doc(inline)]
+    pub use grad_time::zone::{self, Zone, ZoneError, ZoneInfo};
+    #[doc(inline)]
+    pub use grad_time::duration::{self, Duration, DurationError};
+}
+
+pub mod analyze {
+    #[doc(inline)]
+    pub use grad_analyze::entropy::{self, Analyzer, Report, Window};
+
+    #[doc(inline)]
+    pub use grad_analyze::password::{self, Dictionary, Estimate, Estimator};
+
+    #[doc(inline)]
+    pub use grad_analyze::randomness::{self, Outcome, RandomnessError, Test};
+}
+
+pub mod encoding {
+    #[doc(inline)]
+    pub use grad_encoding::{DecodeError, EncodeError, Encoding, Mode};
+}
+
+pub mod hash {
+    #[doc(inline)]
+    pub use grad_hash::{Algorithm, Hasher};
+    #[doc(inline)]
+    pub use grad_hash::check::{self, Entry};
+    #[doc(inline)]
+    pub use grad_hash::mac::{self, Mac, MacAlgorithm};
+    #[doc(inline)]
+    pub use grad_parse::key::Key;
 }
 
 pub mod random {
     #[doc(inline)]
-    pub use grad_rand::byte::{random_byte, random_hex, random_base64};
+    pub use grad_rand::rng::{Backend, RandomSource, SharedSource, StreamSource, rng};
+    #[doc(inline)]
+    pub use grad_rand::generator::Generator;
+    #[doc(inline)]
+    pub use grad_rand::entropy::{EntropyError, bits_per_symbol, entropy, length_for_entropy};
+    #[doc(inline)]
+    pub use grad_rand::byte::{random_byte, write_random_byte, random_hex, random_base64, random_encoded};
+    #[doc(inline)]
+    pub use grad_rand::id::{KsuidSequence, NANOID_LENGTH, SnowflakeSequence, UlidSequence, random_nanoid};
     #[doc(inline)]
     pub use grad_rand::number::random_integer;
     #[doc(inline)]
     pub use grad_rand::digit::random_digit;
     #[doc(inline)]
-    pub use grad_rand::password::random_password;
+    pub use grad_rand::password::{Acceptances, Policy, Requirement, random_password, random_password_with_policy};
+    #[doc(inline)]
+    pub use grad_rand::passphrase::{BuiltinList, WordList, random_passphrase};
+    #[doc(inline)]
+    pub use grad_rand::pronounceable::{Slot, random_pronounceable, repeats_for_entropy, template_entropy};
+    #[doc(inline)]
+    pub use grad_parse::template::Template;
     #[doc(inline)]
-    pub use grad_rand::passphrase::{WordList, random_passphrase};
+    pub use grad_rand::uuid::{UuidFormat, format_uuid, initial_counter, name_uuid, random_uuid, time_uuid};
 }
 
 pub mod cli {
@@ -1,11 +1,21 @@
+use std::fs::File;
 use std::ops::{Range, RangeInclusive};
-use std::io::{Write, stdout};
+use std::path::{Path, PathBuf};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write, stdin, stdout};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use anyhow::anyhow;
+use either::Either;
+use rayon::ThreadPoolBuilder;
 use rayon::prelude::*;
-use tracing::{instrument, trace};
+use tracing::{error, info, instrument, trace, warn};
 
-use grad::time::{local, utc};
-use grad::random::{self, WordList};
+use grad::analyze::{self, Dictionary, Estimate, Estimator, Outcome, Report, Window};
+use grad::analyze::password::Pattern;
+use grad::encoding;
+use grad::hash::{self, Entry, Key};
+use grad::time::{self, duration, parse, unix, utc, zone, Duration, Zone};
+use grad::random::{self, Generator, Policy, RandomSource, Requirement, SharedSource, Slot, StreamSource, WordList};
 use grad::cli::command::*;
 
 mod instrumentation;
@@ -19,97 +29,858 @@ fn write_out(buffer: &[u8]) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Creates the generators used to produce the items of a batch.
+enum Sources {
+    Backend(random::Backend, Option<[u8; 32]>),
+    Stream(SharedSource)
+}
+
+impl Sources {
+    fn generator(&self) -> anyhow::Result<Generator> {
+        match self {
+            Sources::Backend(backend, seed) => Ok(backend.generator(*seed)?),
+            Sources::Stream(source) => Ok(Generator::new(source.clone()))
+        }
+    }
+
+    /// Whether the sources produce the same outputs every time a batch is generated.
+    fn is_deterministic(&self) -> bool {
+        match self {
+            Sources::Backend(_, seed) => seed.is_some(),
+            Sources::Stream(..) => true
+        }
+    }
+
+    /// The number of threads with which to generate a batch.
+    ///
+    /// Streams are consumed by a single thread so that their bytes are assigned to items in order.
+    fn threads(&self) -> usize {
+        match self {
+            Sources::Backend(..) => 0,
+            Sources::Stream(..) => 1
+        }
+    }
+}
+
+/// The number of outputs generated at once when a batch is written in order.
+///
+/// Outputs are buffered until every output in the window has been generated, so this bounds the
+/// memory used by ordered batches regardless of their size.
+const ORDERED_WINDOW: usize = 1 << 16;
+
 macro_rules! batch {
-    ($iterations:expr, $function:expr) => {
+    ($batch:expr, $sources:expr, |$rng:ident| $function:expr) => {
+        batch!($batch, $sources, |$rng, _index| $function)
+    };
+    ($batch:expr, $sources:expr, |$rng:ident, $index:ident| $function:expr) => {
         let mut stdout = stdout();
 
-        if let Some(iterations) = $iterations {
-            (0..iterations)
-                .into_par_iter()
-                .map(|index| {
-                    let buffer: Vec<u8> = $function;
+        if let Some(iterations) = $batch.iterations {
+            let pool = ThreadPoolBuilder::new().num_threads($sources.threads()).build()?;
+            let delimiter = $batch.delimiter();
+            let written = AtomicUsize::new(0);
+
+            let generate = |generator: &mut anyhow::Result<Generator>, index: usize| -> anyhow::Result<Vec<u8>> {
+                let $rng = generator.as_mut().map_err(|error| anyhow!("{}", error))?;
 
-                    trace!(index = index, iterations = iterations - 1);
+                $rng.select(index as u64);
 
-                    (index, buffer)
-                })
-                .try_for_each(|(index, mut buffer)| {
-                    let mut handle = stdout.lock();
+                let $index = index as u64;
+                let buffer: Vec<u8> = $function;
 
-                    // Write the delimiter to the buffer if and only if
-                    // the current message is not the final message.
-                    if index < iterations { buffer.push(b'\n') }
+                $rng.check()?;
 
-                    handle.write_all(&buffer)
-                })?;
+                trace!(index = index, iterations = iterations - 1);
+
+                Ok(buffer)
+            };
+
+            let write = |buffer: anyhow::Result<Vec<u8>>| -> anyhow::Result<()> {
+                let buffer = buffer?;
+                let mut handle = stdout.lock();
+
+                // Unordered outputs are written in the order in which they complete, so
+                // a separator precedes every output except the first to be written.
+                match $batch.mode {
+                    DelimiterMode::Separator => {
+                        if written.fetch_add(1, Ordering::Relaxed) > 0 { handle.write_all(delimiter)? }
+
+                        handle.write_all(&buffer)?;
+                    }
+                    DelimiterMode::Terminator => {
+                        handle.write_all(&buffer)?;
+                        handle.write_all(delimiter)?;
+                    }
+                }
+
+                Ok(())
+            };
+
+            pool.install(|| -> anyhow::Result<()> {
+                if $batch.is_ordered($sources.is_deterministic()) {
+                    let mut buffers = Vec::with_capacity(ORDERED_WINDOW.min(iterations));
+
+                    for start in (0..iterations).step_by(ORDERED_WINDOW) {
+                        (start..iterations.min(start + ORDERED_WINDOW))
+                            .into_par_iter()
+                            .map_init(|| $sources.generator(), &generate)
+                            .collect_into_vec(&mut buffers);
+
+                        buffers.drain(..).try_for_each(&write)?;
+                    }
+
+                    Ok(())
+                }
+                else {
+                    (0..iterations)
+                        .into_par_iter()
+                        .map_init(|| $sources.generator(), &generate)
+                        .try_for_each(&write)
+                }
+            })?;
         }
         else {
-            stdout.write_all(&$function)?;
+            let $rng = &mut $sources.generator()?;
+            let $index = 0u64;
+            let buffer: Vec<u8> = $function;
+
+            $rng.check()?;
+            stdout.write_all(&buffer)?;
         }
 
         stdout.flush()?;
     };
 }
 
+/// The smallest length of an output from its alphabet which carries the requested minimum entropy.
+fn minimum_length(options: &Entropy, alphabet: usize) -> anyhow::Result<usize> {
+    match options.min_entropy {
+        Some(bits) => Ok(random::length_for_entropy(alphabet, bits)?),
+        None => Ok(0)
+    }
+}
+
+/// Resolve the length of each output from its alphabet, the requested length and the minimum entropy,
+/// and report the entropy of the output if requested.
+fn resolve_length(length: Option<usize>, options: &Entropy, alphabet: usize) -> anyhow::Result<usize> {
+    let length = length.unwrap_or(0).max(minimum_length(options, alphabet)?);
+
+    if options.show {
+        eprintln!("{:.2} bits ({} × {:.2} bits)", random::entropy(alphabet, length), length, random::bits_per_symbol(alphabet));
+    }
+
+    Ok(length)
+}
+
+/// Resolve the length of each password as [`resolve_length`] does, accounting for the entropy lost to
+/// the password policy.
+fn resolve_password_length(length: Option<usize>, options: &Entropy, chars: &[char], policy: &Policy) -> anyhow::Result<usize> {
+    if policy.is_empty() { return resolve_length(length, options, chars.len()) }
+
+    let mut length = length.unwrap_or(0).max(minimum_length(options, chars.len())?);
+
+    if let Some(bits) = options.min_entropy {
+        length = policy.length_for_entropy(chars, length, bits)?;
+    }
+
+    let acceptance = policy.check(chars, length)?;
+
+    if options.show {
+        eprintln!(
+            "{:.2} bits ({} × {:.2} bits, less {:.2} bits for the password policy)",
+            random::entropy(chars.len(), length) + acceptance.log2(),
+            length,
+            random::bits_per_symbol(chars.len()),
+            -acceptance.log2()
+        );
+    }
+
+    Ok(length)
+}
+
+/// The instant at which a batch of time-based identifiers is generated, in milliseconds since the Unix epoch.
+fn now(clock: &Clock) -> anyhow::Result<u64> {
+    match clock.timestamp {
+        Some(timestamp) => u64::try_from(timestamp.millis())
+            .map_err(|_| anyhow!("The timestamp {} ms precedes the Unix epoch.", timestamp.millis())),
+        None => Ok(unix::now_millis()?)
+    }
+}
+
+/// Write a batch in order unless unordered output was requested, for identifiers which increase with their index.
+fn in_order(batch: Batch) -> Batch {
+    Batch { ordered: !batch.unordered, ..batch }
+}
+
+/// Read the whole of a file, or of standard input if the path is omitted or is -.
+fn read_input(path: Option<PathBuf>) -> anyhow::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+
+    match path {
+        Some(path) if path.as_os_str() != "-" => { File::open(path)?.read_to_end(&mut buffer)?; }
+        _ => { stdin().lock().read_to_end(&mut buffer)?; }
+    }
+
+    Ok(buffer)
+}
+
+/// Write an entropy report in the format of the ent program.
+fn write_entropy_report(mut handle: impl Write, report: &Report, histogram: bool) -> std::io::Result<()> {
+    if histogram {
+        writeln!(handle, "Value Char Occurrences Fraction")?;
+
+        for (value, &count) in report.histogram.iter().enumerate().filter(|(_, count)| **count > 0) {
+            let char = if (0x20..0x7f).contains(&value) { value as u8 as char } else { ' ' };
+
+            writeln!(handle, "{:>5}   {}   {:>10}   {:.6}", value, char, count, count as f64 / report.length as f64)?;
+        }
+
+        writeln!(handle)?;
+    }
+
+    writeln!(handle, "Entropy = {:.6} bits per byte.", report.entropy)?;
+    writeln!(handle)?;
+
+    if let Some(compression) = report.compression {
+        writeln!(handle, "Optimum compression would reduce the size")?;
+        writeln!(handle, "of this {} byte file by {:.0} percent.", report.length, compression * 100.0)?;
+        writeln!(handle)?;
+    }
+
+    writeln!(handle, "Chi square distribution for {} samples is {:.2}, and randomly", report.length, report.chi_square)?;
+    writeln!(handle, "would exceed this value {:.2} percent of the times.", report.chi_square_p_value * 100.0)?;
+    writeln!(handle)?;
+    writeln!(handle, "Arithmetic mean value of data bytes is {:.4} (127.5 = random).", report.mean)?;
+
+    match (report.monte_carlo_pi, report.monte_carlo_pi_error) {
+        (Some(pi), Some(error)) => writeln!(handle, "Monte Carlo value for Pi is {:.9} (error {:.2} percent).", pi, error * 100.0)?,
+        _ => writeln!(handle, "Monte Carlo value for Pi is undefined: fewer than six bytes were analyzed.")?
+    }
+
+    match report.serial_correlation {
+        Some(correlation) => writeln!(handle, "Serial correlation coefficient is {:.6} (totally uncorrelated = 0.0).", correlation)?,
+        None if report.length == 0 => writeln!(handle, "Serial correlation coefficient is undefined: no bytes were analyzed.")?,
+        None => writeln!(handle, "Serial correlation coefficient is undefined: every byte is the same.")?
+    }
+
+    Ok(())
+}
+
+/// Write the estimated strength of a password and the patterns it was split into.
+fn write_password_estimate(mut handle: impl Write, estimate: &Estimate) -> std::io::Result<()> {
+    let times = &estimate.crack_times;
+
+    writeln!(handle, "Score: {}/4", estimate.score)?;
+    writeln!(handle, "Guesses: {:.3e} ({:.1} bits)", estimate.guesses, estimate.entropy)?;
+    writeln!(handle, "Crack time, online attack at 100 guesses per hour: {}", analyze::password::humanize(times.online_throttled))?;
+    writeln!(handle, "Crack time, online attack at 10 guesses per second: {}", analyze::password::humanize(times.online_unthrottled))?;
+    writeln!(handle, "Crack time, offline attack on a slow hash: {}", analyze::password::humanize(times.offline_slow_hash))?;
+    writeln!(handle, "Crack time, offline attack on a fast hash: {}", analyze::password::humanize(times.offline_fast_hash))?;
+
+    for candidate in &estimate.sequence {
+        let pattern = match &candidate.pattern {
+            Pattern::Dictionary { word, dictionary, rank, reversed, l33t, uppercase } => {
+                let variations: String = [(*reversed, ", reversed"), (*l33t, ", l33t"), (*uppercase, ", uppercase")].iter()
+                    .filter(|(applies, _)| *applies)
+                    .map(|(_, name)| *name)
+                    .collect();
+
+                format!("word '{}' from {} (rank {}{})", word, dictionary, rank, variations)
+            }
+            Pattern::Spatial { turns, shifted } => format!("keyboard pattern ({} turns, {} shifted)", turns, shifted),
+            Pattern::Repeat { base, count } => format!("'{}' repeated {} times", base, count),
+            Pattern::Sequence { ascending: true } => String::from("ascending sequence"),
+            Pattern::Sequence { ascending: false } => String::from("descending sequence"),
+            Pattern::Date { year, .. } => format!("date in {}", year),
+            Pattern::Year { year } => format!("year {}", year),
+            Pattern::Bruteforce => String::from("no pattern")
+        };
+
+        writeln!(handle, "  {:<24} {} ({:.3e} guesses)", candidate.token, pattern, candidate.guesses)?;
+    }
+
+    Ok(())
+}
+
+/// Write a table of the p-values of each test of randomness and whether it passed.
+fn write_randomness_outcomes(mut handle: impl Write, outcomes: &[Outcome], significance: f64) -> std::io::Result<()> {
+    writeln!(handle, "{:<22} {:>9} {:>19}  Result", "Test", "Parameter", "p-values")?;
+
+    for outcome in outcomes {
+        let parameter = outcome.parameter.map(|parameter| parameter.to_string()).unwrap_or_default();
+        let p_values: Vec<String> = outcome.p_values.iter().map(|p_value| format!("{:.6}", p_value)).collect();
+        let result = if outcome.passed(significance) { "PASS" } else { "FAIL" };
+
+        writeln!(handle, "{:<22} {:>9} {:>19}  {}", outcome.test.name(), parameter, p_values.join(" "), result)?;
+    }
+
+    Ok(())
+}
+
+/// Write a table of the statistics of each window.
+fn write_windows(mut handle: impl Write, windows: impl Iterator<Item = std::io::Result<Window>>) -> std::io::Result<()> {
+    writeln!(handle, "{:>12} {:>10} {:>9} {:>12} {:>9} {:>9}", "Offset", "Length", "Entropy", "Chi-square", "p-value", "Mean")?;
+
+    for window in windows {
+        let window = window?;
+
+        writeln!(
+            handle,
+            "{:>12} {:>10} {:>9.6} {:>12.2} {:>9.6} {:>9.4}",
+            window.offset, window.length, window.entropy, window.chi_square, window.chi_square_p_value, window.mean
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Open a file, or standard input if the path is -.
+fn open_input(path: &Path) -> std::io::Result<Box<dyn Read>> {
+    if path.as_os_str() == "-" { Ok(Box::new(stdin().lock())) }
+    else { Ok(Box::new(File::open(path)?)) }
+}
+
+/// Digest files in parallel, yielding their digests in the order of the files.
+fn digest_paths<'a, P, F>(paths: &'a [P], digest: F) -> impl Iterator<Item = (&'a P, std::io::Result<Vec<u8>>)> + 'a
+where
+    P: AsRef<Path> + Sync + 'a,
+    F: Fn(&Path) -> std::io::Result<Vec<u8>> + Sync + 'a
+{
+    paths.chunks(ORDERED_WINDOW).flat_map(move |chunk| {
+        let digests: Vec<_> = chunk.par_iter().map(|path| digest(path.as_ref())).collect();
+
+        chunk.iter().zip(digests)
+    })
+}
+
+/// Encode a digest for a line of a checksum file, which cannot hold whitespace or invalid UTF-8.
+fn encode_digest(encoding: &encoding::Encoding, digest: &[u8]) -> anyhow::Result<String> {
+    String::from_utf8(encoding.encode(digest)?).ok()
+        .filter(|digest| !digest.contains(char::is_whitespace))
+        .ok_or(anyhow!("Digests encoded with {} cannot be written to a checksum file.", encoding.name()))
+}
+
+/// Write a checksum line for each file.
+fn write_checksums<F>(files: &[PathBuf], encoding: &encoding::Encoding, digest: F) -> anyhow::Result<()>
+where
+    F: Fn(&Path) -> std::io::Result<Vec<u8>> + Sync
+{
+    let mut handle = stdout().lock();
+    let mut failures = 0;
+
+    for (path, digest) in digest_paths(files, digest) {
+        match digest {
+            Ok(digest) => writeln!(handle, "{}", Entry::new(encode_digest(encoding, &digest)?, path.to_string_lossy()))?,
+            Err(cause) => {
+                error!("{}: {}", path.display(), cause);
+                failures += 1;
+            }
+        }
+    }
+
+    handle.flush()?;
+
+    match failures {
+        0 => Ok(()),
+        _ => Err(anyhow!("{} of {} files could not be read.", failures, files.len()))
+    }
+}
+
+fn verify_checksums(algorithm: hash::Algorithm, encoding: &encoding::Encoding, files: &[PathBuf]) -> anyhow::Result<()> {
+    let mut handle = stdout().lock();
+    let mut mismatches = 0;
+    let mut unreadable = 0;
+    let mut invalid = 0;
+
+    for file in files {
+        let contents = read_input(Some(file.clone())).and_then(|contents| Ok(String::from_utf8(contents)?));
+
+        let contents = match contents {
+            Ok(contents) => contents,
+            Err(cause) => {
+                error!("{}: {}", file.display(), cause);
+                invalid += 1;
+                continue
+            }
+        };
+
+        let mut entries = Vec::new();
+        let mut malformed = 0;
+
+        for entry in hash::check::parse(&contents) {
+            let expected = entry.map_err(anyhow::Error::from)
+                .and_then(|entry| Ok((encoding.decode(entry.digest.as_bytes(), encoding::Mode::Lenient)?, entry)));
+
+            match expected {
+                Ok(expected) => entries.push(expected),
+                Err(cause) => {
+                    warn!("{}: {}", file.display(), cause);
+                    malformed += 1;
+                }
+            }
+        }
+
+        if entries.is_empty() {
+            error!("{}: No properly formatted checksum lines were found.", file.display());
+            invalid += 1;
+            continue
+        }
+
+        if malformed > 0 { warn!("{}: {} lines are improperly formatted.", file.display(), malformed) }
+
+        let names: Vec<&str> = entries.iter().map(|(_, entry)| entry.name.as_str()).collect();
+
+        for ((expected, entry), (_, digest)) in entries.iter().zip(digest_paths(&names, |path| algorithm.hash_reader(open_input(path)?))) {
+            match digest {
+                Ok(digest) if digest == *expected => writeln!(handle, "{}: OK", entry.name)?,
+                Ok(..) => {
+                    writeln!(handle, "{}: FAILED", entry.name)?;
+                    mismatches += 1;
+                }
+                Err(cause) => {
+                    writeln!(handle, "{}: FAILED open or read", entry.name)?;
+                    error!("{}: {}", entry.name, cause);
+                    unreadable += 1;
+                }
+            }
+        }
+    }
+
+    handle.flush()?;
+
+    let failures: Vec<String> = [
+        (invalid, "checksum files could not be read or held no properly formatted lines"),
+        (mismatches, "computed checksums did not match"),
+        (unreadable, "listed files could not be read")
+    ].iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, failure)| format!("{} {}", count, failure))
+        .collect();
+
+    if failures.is_empty() { Ok(()) } else { Err(anyhow!("{}.", failures.join(", and "))) }
+}
+
+/// Move a timestamp forward by a duration, or back if `subtract` is set, and write the result.
+fn shift_timestamp(arithmetic: TimeArithmetic, subtract: bool) -> anyhow::Result<()> {
+    let zone = arithmetic.tz.map(|zone| zone.parse::<Zone>()).transpose()?;
+    let datetime = parse::parse_or_now(&arithmetic.timestamp, zone).map_err(|error| anyhow!("{}: {}", arithmetic.timestamp, error))?;
+    let duration: Duration = arithmetic.duration.parse()?;
+    let duration = if subtract { -duration } else { duration };
+
+    let result = duration::add(&datetime, &duration, zone)?;
+    let format = arithmetic.format.unwrap_or(time::Format::Rfc3339);
+
+    writeln!(stdout(), "{}", time::format(&result, &format, arithmetic.precision)?)?;
+
+    Ok(())
+}
+
 #[instrument]
 fn execute(command: Command) -> anyhow::Result<()> {
     match command {
         Command::Create { command } => match command {
-            CreateCommand::Timestamp { command, format } => {
-                let buffer = match command {
-                    Some(TimestampCommand::Utc) => match format {
-                        Some(TimestampFormat::ISO8601) => utc::now_iso8601(),
-                        Some(TimestampFormat::RFC2822) => utc::now_rfc2822(),
-                        Some(TimestampFormat::RFC3339) => utc::now_rfc3339(),
-                        None => utc::now()
+            CreateCommand::Timestamp { command, format, precision, tz } => {
+                let zone = match (command, tz) {
+                    (Some(TimestampCommand::Utc), _) => Zone::Utc,
+                    (Some(TimestampCommand::Local), _) | (None, None) => Zone::Local,
+                    (None, Some(tz)) => tz.parse()?
+                };
+
+                let now = zone.now();
+
+                let format = format.unwrap_or(time::Format::Simple);
+
+                write_out(time::format(&now, &format, precision)?.as_bytes())?;
+            }
+            CreateCommand::Uuid { version, namespace, name, clock, output, batch } => {
+                let sources = Sources::Backend(random::Backend::default(), None);
+                let format = output.format;
+
+                match version.version() {
+                    UuidVersion::V4 => {
+                        batch!(batch, sources, |rng| random::format_uuid(random::random_uuid(rng), format, output.uppercase));
+                    }
+                    UuidVersion::V5 => {
+                        let namespace = namespace.ok_or(anyhow!("A name-based UUID requires a namespace."))?;
+                        let name = name.ok_or(anyhow!("A name-based UUID requires a name."))?;
+                        let uuid = random::name_uuid(namespace.into(), name.as_bytes());
+
+                        write_out(&random::format_uuid(uuid, format, output.uppercase))?;
                     }
-                    Some(TimestampCommand::Local) | None => match format {
-                        Some(TimestampFormat::ISO8601) => local::now_iso8601(),
-                        Some(TimestampFormat::RFC2822) => local::now_rfc2822(),
-                        Some(TimestampFormat::RFC3339) => local::now_rfc3339(),
-                        None => local::now()
+                    UuidVersion::V7 => {
+                        let timestamp = now(&clock)?;
+                        let mut generator = sources.generator()?;
+                        let counter = random::initial_counter(&mut generator);
+
+                        generator.check()?;
+                        let batch = in_order(batch);
+
+                        batch!(
+                            batch,
+                            sources,
+                            |rng, index| random::format_uuid(random::time_uuid(rng, timestamp, counter + index), format, output.uppercase)
+                        );
                     }
+                }
+            }
+        }
+        Command::Convert { command } => match command {
+            ConvertCommand::Timestamp { input, from, from_tz, tz, format, precision } => {
+                let from_tz = from_tz.map_or(Ok(Zone::Utc), |zone| zone.parse())?;
+                let tz = tz.map(|zone| zone.parse::<Zone>()).transpose()?;
+                let format = format.unwrap_or(time::Format::Rfc3339);
+                let inputs = match input {
+                    Some(input) => vec![input],
+                    None => String::from_utf8(read_input(None)?)?.lines().map(String::from).collect()
                 };
 
-                write_out(&buffer)?;
+                let mut handle = stdout().lock();
+
+                for input in inputs.iter().map(|input| input.trim()).filter(|input| !input.is_empty()) {
+                    let (datetime, detected) = parse::parse(input, from, from_tz).map_err(|error| anyhow!("{}: {}", input, error))?;
+                    let datetime = tz.map_or(datetime, |zone| zone.convert(&datetime));
+
+                    info!("Parsed {} in the {} format.", input, detected.name());
+
+                    writeln!(handle, "{}", time::format(&datetime, &format, precision)?)?;
+                }
+
+                handle.flush()?;
             }
         }
-        Command::Random { command } => match command {
-            RandomCommand::Byte { length } => {
-                let buffer = random::random_byte(length);
+        Command::Time { command } => match command {
+            TimeCommand::Zones { filter } => {
+                let filter = filter.map(|filter| filter.to_lowercase());
+                let mut handle = stdout().lock();
 
-                write_out(&buffer)?;
+                for zone in zone::zones(&utc::now()) {
+                    if filter.as_ref().is_some_and(|filter| !zone.name.to_lowercase().contains(filter)) { continue }
+
+                    let line = format!(
+                        "{:<32} {} {:<6} {}",
+                        zone.name,
+                        zone.offset,
+                        zone.abbreviation.as_deref().unwrap_or(""),
+                        if zone.dst { "DST" } else { "" }
+                    );
+
+                    writeln!(handle, "{}", line.trim_end())?;
+                }
+
+                handle.flush()?;
             }
-            RandomCommand::Hex { uppercase, length, batch } => {
-                batch!(batch.iterations, random::random_hex(uppercase, length));
+            TimeCommand::Add { arithmetic } => shift_timestamp(arithmetic, false)?,
+            TimeCommand::Sub { arithmetic } => shift_timestamp(arithmetic, true)?,
+            TimeCommand::Diff { start, end, tz, unit, iso8601 } => {
+                let zone = tz.map(|zone| zone.parse::<Zone>()).transpose()?;
+                let read = |input: &str| parse::parse_or_now(input, zone).map_err(|error| anyhow!("{}: {}", input, error));
+                let delta = read(&end)? - read(&start)?;
+
+                match unit {
+                    Some(unit) => writeln!(stdout(), "{}", duration::in_unit(delta, unit))?,
+                    None if iso8601 => writeln!(stdout(), "{}", Duration::from(delta))?,
+                    None => writeln!(stdout(), "{}", duration::humanize(delta))?
+                }
             }
-            RandomCommand::Base64 { url, length, batch } => {
-                batch!(batch.iterations, random::random_base64(url, length));
+        }
+        Command::Encode { encoding, input, hrp, no_newline } => {
+            let mut buffer = encoding.with_hrp(hrp).encode(&read_input(input)?)?;
+
+            if !(no_newline || encoding == Encoding::Raw) { buffer.push(b'\n') }
+
+            write_out(&buffer)?;
+        }
+        Command::Decode { encoding, input, hrp, lenient } => {
+            let mode = if lenient { encoding::Mode::Lenient } else { encoding::Mode::Strict };
+
+            write_out(&encoding.with_hrp(hrp).decode(&read_input(input)?, mode)?)?;
+        }
+        Command::Analyze { command } => match command {
+            AnalyzeCommand::Entropy { input, window, step, histogram, json } => {
+                let mut handle = stdout().lock();
+
+                match window {
+                    Some(window) => {
+                        let window = usize::try_from(u64::from(window))?;
+                        let step = step.map(|step| usize::try_from(u64::from(step))).transpose()?.unwrap_or(window);
+
+                        if window == 0 || step == 0 { return Err(anyhow!("The window and step must be at least one byte.")) }
+
+                        let reader = BufReader::new(open_input(input.as_deref().unwrap_or(Path::new("-")))?);
+                        let windows = analyze::entropy::windows(reader, window, step);
+
+                        if json { writeln!(handle, "{}", serde_json::to_string_pretty(&windows.collect::<std::io::Result<Vec<_>>>()?)?)? }
+                        else { write_windows(&mut handle, windows)? }
+                    }
+                    None => {
+                        let mut analyzer = analyze::Analyzer::new();
+
+                        std::io::copy(&mut open_input(input.as_deref().unwrap_or(Path::new("-")))?, &mut analyzer)?;
+
+                        let report = analyzer.finish();
+
+                        if json { writeln!(handle, "{}", serde_json::to_string_pretty(&report)?)? }
+                        else { write_entropy_report(&mut handle, &report, histogram)? }
+                    }
+                }
+
+                handle.flush()?;
             }
-            RandomCommand::Digit { length, batch } => {
-                batch!(batch.iterations, random::random_digit(length));
+            AnalyzeCommand::Password { input, wordlist, list, json } => {
+                let words = match (wordlist, list) {
+                    (Some(path), ..) => WordList::from_path(path)?,
+                    (.., Some(list)) => WordList::builtin(list)?,
+                    (None, None) => WordList::default()
+                };
+
+                let dictionaries = vec![Dictionary::common_passwords(), Dictionary::uniform(words.name(), words.inner())];
+                let estimator = Estimator::new(dictionaries, utc::year());
+                let input = String::from_utf8(read_input(input)?)?;
+                let estimates = input.lines().map(|password| estimator.estimate(password));
+                let mut handle = stdout().lock();
+
+                if json {
+                    writeln!(handle, "{}", serde_json::to_string_pretty(&estimates.collect::<Vec<_>>())?)?;
+                }
+                else {
+                    for (index, estimate) in estimates.enumerate() {
+                        if index > 0 { writeln!(handle)? }
+
+                        write_password_estimate(&mut handle, &estimate)?;
+                    }
+                }
+
+                handle.flush()?;
             }
-            RandomCommand::Integer { range, batch } => {
-                trace!("{}", range.clone().either(|range| format!("{:?}", range), |range| format!("{:?}", range)));
-                batch!(
-                    batch.iterations,
-                    range.clone().either(
-                        |range| random::random_integer(Range::from(range.clone())),
-                        |range| random::random_integer(RangeInclusive::from(range.clone()))
-                    )
-                );
+            AnalyzeCommand::Randomness { input, significance, json } => {
+                if !(0.0..1.0).contains(&significance) { return Err(anyhow!("The significance must be at least 0 and less than 1.")) }
+
+                let outcomes = analyze::randomness::run(&analyze::randomness::bits(&read_input(input)?))?;
+                let failed = outcomes.iter().filter(|outcome| !outcome.passed(significance)).count();
+                let mut handle = stdout().lock();
+
+                if json { writeln!(handle, "{}", serde_json::to_string_pretty(&outcomes)?)? }
+                else { write_randomness_outcomes(&mut handle, &outcomes, significance)? }
+
+                handle.flush()?;
+
+                if failed > 0 {
+                    return Err(anyhow!("{} of {} tests failed at a significance of {}.", failed, outcomes.len(), significance));
+                }
             }
-            RandomCommand::Password { include, length, batch } => {
-                let chars: Vec<char> = include.iter()
-                    .flat_map(Vec::from)
-                    .collect();
+        }
+        Command::Hash { files, algorithm, encoding, hrp, check } => {
+            let encoding = encoding.with_hrp(hrp);
+            let files = if files.is_empty() { vec![PathBuf::from("-")] } else { files };
+
+            if algorithm.is_legacy() {
+                eprintln!("Warning: {} is broken and should be used only to verify existing checksums.", algorithm.name());
+            }
+
+            if check { verify_checksums(algorithm, &encoding, &files)? }
+            else { write_checksums(&files, &encoding, |path| algorithm.hash_reader(open_input(path)?))? }
+        }
+        Command::Mac { files, key, algorithm, encoding, hrp, verify } => {
+            let encoding = encoding.with_hrp(hrp);
+            let files = if files.is_empty() { vec![PathBuf::from("-")] } else { files };
+
+            let key = match key {
+                Key::Bytes(key) => key,
+                Key::File(path) => std::fs::read(path)?,
+                Key::Random(length) => {
+                    let key = random::random_byte(&mut random::Backend::default().generator(None)?, length);
 
-                batch!(batch.iterations, random::random_password(&chars, length));
+                    eprintln!("Key: {}", String::from_utf8(encoding::Encoding::Hex.encode(&key)?)?);
+
+                    key
+                }
+            };
+
+            // Reject unusable keys before reading any input.
+            algorithm.mac(&key)?;
+
+            if key.len() < algorithm.key_length() {
+                warn!("The key has {} bytes, fewer than the {} bytes recommended for {}.", key.len(), algorithm.key_length(), algorithm.name());
             }
-            RandomCommand::Passphrase { separator, length, batch } => {
-                let words = WordList::default();
 
-                batch!(batch.iterations, random::random_passphrase(&words, &separator, length));
+            match verify {
+                Some(expected) => {
+                    let [path] = files.as_slice() else { return Err(anyhow!("Only one input can be verified at a time.")) };
+
+                    let expected = encoding.decode(expected.as_bytes(), encoding::Mode::Lenient)?;
+                    let computed = algorithm.mac_reader(&key, open_input(path)?)?;
+
+                    if !hash::mac::verify(&computed, &expected) { return Err(anyhow!("The code does not match.")) }
+
+                    write_out(b"OK\n")?;
+                }
+                None => write_checksums(&files, &encoding, |path| algorithm.mac_reader(&key, open_input(path)?))?
+            }
+        }
+        Command::Random { seed, rng, rng_file, command } => {
+            let sources = match rng_file {
+                Some(path) => {
+                    let reader: Box<dyn Read + Send> = if path.as_os_str() == "-" { Box::new(stdin()) }
+                        else { Box::new(BufReader::new(File::open(path)?)) };
+
+                    Sources::Stream(SharedSource::new(StreamSource::new(reader)))
+                }
+                None => Sources::Backend(rng, seed.map(<[u8; 32]>::from))
+            };
+
+            info!("Using the {} random source.", sources.generator()?.name());
+
+            match *command {
+                RandomCommand::Byte { length, output, encoding, hrp, batch } => {
+                    match (encoding, output) {
+                        (Some(encoding), ..) => {
+                            let encoding = encoding.with_hrp(hrp);
+                            let length = usize::try_from(u64::from(length))?;
+
+                            batch!(batch, sources, |rng| random::random_encoded(rng, &encoding, length)?);
+                        }
+                        (None, Some(path)) => {
+                            let mut file = BufWriter::new(File::create(path)?);
+
+                            random::write_random_byte(&mut sources.generator()?, &mut file, length.into())?;
+                            file.flush()?;
+                        }
+                        (None, None) if batch.iterations.is_some() => {
+                            let length = usize::try_from(u64::from(length))?;
+
+                            batch!(batch, sources, |rng| random::random_byte(rng, length));
+                        }
+                        (None, None) => {
+                            let mut handle = stdout().lock();
+
+                            random::write_random_byte(&mut sources.generator()?, &mut handle, length.into())?;
+                            handle.flush()?;
+                        }
+                    }
+                }
+                RandomCommand::Hex { uppercase, length, entropy, batch } => {
+                    let length = resolve_length(length, &entropy, 256)?;
+
+                    batch!(batch, sources, |rng| random::random_hex(rng, uppercase, length));
+                }
+                RandomCommand::Base64 { url, length, entropy, batch } => {
+                    let length = resolve_length(length, &entropy, 256)?;
+
+                    batch!(batch, sources, |rng| random::random_base64(rng, url, length));
+                }
+                RandomCommand::Digit { length, entropy, batch } => {
+                    let length = resolve_length(length, &entropy, 10)?;
+
+                    batch!(batch, sources, |rng| random::random_digit(rng, length));
+                }
+                RandomCommand::Integer { range, batch } => {
+                    trace!("{}", range.clone().either(|range| format!("{:?}", range), |range| format!("{:?}", range)));
+                    batch!(
+                        batch,
+                        sources,
+                        |rng| match range.clone() {
+                            Either::Left(range) => random::random_integer(rng, Range::from(range)),
+                            Either::Right(range) => random::random_integer(rng, RangeInclusive::from(range))
+                        }
+                    );
+                }
+                RandomCommand::Password { include, chars, exclude, no_ambiguous, minimums, length, entropy, batch } => {
+                    let include = match (include.is_empty(), chars.is_empty()) {
+                        (true, true) => vec![CharacterSet::LowerCase, CharacterSet::UpperCase, CharacterSet::Digit],
+                        _ => include
+                    };
+
+                    let mut chars: Vec<char> = include.iter()
+                        .flat_map(Vec::from)
+                        .chain(chars.iter().flat_map(|spec| spec.iter().copied()))
+                        .filter(|char| !exclude.iter().any(|spec| spec.contains(char)))
+                        .filter(|char| !(no_ambiguous && AMBIGUOUS.contains(char)))
+                        .collect();
+
+                    // Duplicate characters would bias the distribution and overstate its entropy.
+                    chars.sort();
+                    chars.dedup();
+
+                    if chars.is_empty() { return Err(anyhow!("Every character has been excluded from the password.")) }
+
+                    let policy = Policy::new(minimums.iter()
+                        .map(|(set, minimum)| Requirement::new(Vec::from(set), *minimum))
+                        .collect());
+
+                    let length = resolve_password_length(length, &entropy, &chars, &policy)?;
+
+                    batch!(batch, sources, |rng| random::random_password_with_policy(rng, &chars, &policy, length));
+                }
+                RandomCommand::Passphrase { separator, wordlist, list, length, entropy, batch } => {
+                    let words = match (wordlist, list) {
+                        (Some(path), ..) => WordList::from_path(path)?,
+                        (.., Some(list)) => WordList::builtin(list)?,
+                        (None, None) => WordList::default()
+                    };
+
+                    info!("Using the {} word list with {} words ({:.2} bits per word).", words.name(), words.len(), words.bits_per_word());
+
+                    let length = resolve_length(length, &entropy, words.len())?;
+
+                    batch!(batch, sources, |rng| random::random_passphrase(rng, &words, &separator, length));
+                }
+                RandomCommand::Uuid { output, batch } => {
+                    let format = output.format;
+
+                    batch!(batch, sources, |rng| random::format_uuid(random::random_uuid(rng), format, output.uppercase));
+                }
+                RandomCommand::Ulid { clock, batch } => {
+                    let mut generator = sources.generator()?;
+                    let ulids = random::UlidSequence::new(&mut generator, now(&clock)?)?;
+
+                    generator.check()?;
+
+                    let batch = in_order(batch);
+
+                    batch!(batch, sources, |_rng, index| ulids.get(index));
+                }
+                RandomCommand::Ksuid { clock, batch } => {
+                    let ksuids = random::KsuidSequence::new(now(&clock)?)?;
+
+                    batch!(batch, sources, |rng| ksuids.generate(rng));
+                }
+                RandomCommand::Nanoid { alphabet, length, entropy, batch } => {
+                    let length = length.or(entropy.min_entropy.is_none().then_some(random::NANOID_LENGTH));
+                    let length = resolve_length(length, &entropy, alphabet.len())?;
+
+                    batch!(batch, sources, |rng| random::random_nanoid(rng, &alphabet, length));
+                }
+                RandomCommand::Snowflake { epoch, worker, clock, batch } => {
+                    let epoch = u64::try_from(epoch.millis())
+                        .map_err(|_| anyhow!("The epoch {} ms precedes the Unix epoch.", epoch.millis()))?;
+
+                    let snowflakes = random::SnowflakeSequence::new(now(&clock)?, epoch, worker)?;
+                    let batch = in_order(batch);
+
+                    batch!(batch, sources, |_rng, index| snowflakes.get(index)?.to_string().into_bytes());
+                }
+                RandomCommand::Pronounceable { template, entropy, batch } => {
+                    let slots: Vec<Slot> = template.to_vec();
+
+                    let repeats = match entropy.min_entropy {
+                        Some(minimum) => random::repeats_for_entropy(&slots, minimum)?,
+                        None => 1
+                    };
+
+                    let slots = slots.repeat(repeats);
+
+                    if entropy.show {
+                        eprintln!(
+                            "{:.2} bits ({} characters from the template {}, {:.2} bits per character)",
+                            random::template_entropy(&slots),
+                            slots.len(),
+                            template.to_string().repeat(repeats),
+                            random::template_entropy(&slots) / slots.len() as f64
+                        );
+                    }
+
+                    batch!(batch, sources, |rng| random::random_pronounceable(rng, &slots));
+                }
             }
         }
     }
@@ -117,10 +888,20 @@ fn execute(command: Command) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Whether an error was caused by the reader of standard output closing the pipe early, as `head` does.
+fn is_broken_pipe(error: &anyhow::Error) -> bool {
+    error.chain()
+        .filter_map(|cause| cause.downcast_ref::<std::io::Error>())
+        .any(|cause| cause.kind() == ErrorKind::BrokenPipe)
+}
+
 fn main() -> anyhow::Result<()> {
     let arguments = grad::cli::parse()?;
 
     instrumentation::init_tracing(arguments.verbosity)?;
 
-    execute(arguments.command)
+    match execute(arguments.command) {
+        Err(error) if is_broken_pipe(&error) => Ok(()),
+        result => result
+    }
 }